@@ -0,0 +1,573 @@
+//! Minimal desktop front panel for the ID-52A Plus, built on the public
+//! `civ_protocol` API only.
+//!
+//! ```text
+//! cargo run -p civ-protocol --example egui_panel --features gui-example -- [--port NAME] [--baud N] [--sim]
+//! ```
+//!
+//! The `Radio` lives on a worker thread that polls frequency, mode and the
+//! S-meter and executes requests from the window, talking to the GUI over two
+//! channels. This is the intended embedding pattern: `Radio` is `Send` but
+//! blocking, so keep it off the UI thread. `--sim` swaps the serial port for
+//! a small in-process stand-in so the panel can be tried without a radio.
+
+use std::io;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use civ_protocol::command::cmd;
+use civ_protocol::protocol::{ADDR_CONTROLLER, ADDR_ID52, Frame, NG, OK};
+use civ_protocol::transport::Transport;
+use civ_protocol::transport::serial::{auto_detect_baud, find_id52_port, open_port};
+use civ_protocol::{Frequency, OperatingMode, Radio, RadioConfig, bcd};
+use eframe::egui;
+
+/// How often the worker polls the radio.
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Modes offered as buttons, in the same order as the TUI's mode cycle.
+const MODES: [OperatingMode; 5] = [
+    OperatingMode::Fm,
+    OperatingMode::FmN,
+    OperatingMode::Am,
+    OperatingMode::AmN,
+    OperatingMode::Dv,
+];
+
+/// Baud rates offered in the connect dialog; `None` auto-detects.
+const BAUD_RATES: [Option<u32>; 5] = [None, Some(9600), Some(19200), Some(38400), Some(115200)];
+
+const USAGE: &str = "usage: egui_panel [--port NAME] [--baud N] [--sim]";
+
+fn main() -> eframe::Result {
+    let target = match parse_args(std::env::args().skip(1)) {
+        Ok(target) => target,
+        Err(msg) => {
+            eprintln!("{msg}\n{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([380.0, 240.0]),
+        ..Default::default()
+    };
+    eframe::run_native(
+        "CI-V Panel",
+        options,
+        Box::new(move |cc| Ok(Box::new(Panel::new(&cc.egui_ctx, target)))),
+    )
+}
+
+/// Parse the command line. Returns the target to connect to at startup, if any.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Target>, String> {
+    let mut port = None;
+    let mut baud = None;
+    let mut sim = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => port = Some(args.next().ok_or("--port needs a value")?),
+            "--baud" => {
+                let value = args.next().ok_or("--baud needs a value")?;
+                baud = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid baud: {value}"))?,
+                );
+            }
+            "--sim" => sim = true,
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    Ok(match (sim, port) {
+        (true, _) => Some(Target::Sim),
+        (false, Some(name)) => Some(Target::Port { name, baud }),
+        (false, None) => None,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Worker thread
+// ---------------------------------------------------------------------------
+
+/// What to connect to.
+#[derive(Debug, Clone)]
+enum Target {
+    /// A serial port; `baud: None` auto-detects.
+    Port { name: String, baud: Option<u32> },
+    /// The in-process simulator.
+    Sim,
+}
+
+/// Requests from the window to the worker.
+enum Request {
+    SetFrequency(Frequency),
+    SetMode(OperatingMode),
+}
+
+/// Updates from the worker to the window.
+enum Update {
+    Connected(String),
+    State {
+        frequency: Option<Frequency>,
+        mode: Option<OperatingMode>,
+        s_meter: Option<u16>,
+    },
+    Error(String),
+    Disconnected,
+}
+
+/// Open `target`, describing the connection for the status line.
+fn connect(target: &Target) -> civ_protocol::Result<(Radio, String)> {
+    match target {
+        Target::Sim => Ok((
+            Radio::new(Box::new(SimTransport::default()), RadioConfig::default()),
+            "simulator".to_string(),
+        )),
+        Target::Port { name, baud } => {
+            let (baud_rate, transport) = match baud {
+                Some(baud) => (*baud, open_port(name, *baud)?),
+                None => auto_detect_baud(name)?,
+            };
+            let config = RadioConfig {
+                baud_rate,
+                ..RadioConfig::default()
+            };
+            Ok((
+                Radio::new(Box::new(transport), config),
+                format!("{name} @ {baud_rate} baud"),
+            ))
+        }
+    }
+}
+
+/// Spawn the worker for `target`. Dropping the returned sender disconnects.
+fn spawn_worker(
+    target: Target,
+    ctx: egui::Context,
+) -> (mpsc::Sender<Request>, mpsc::Receiver<Update>) {
+    let (req_tx, req_rx) = mpsc::channel();
+    let (update_tx, update_rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let send = |update: Update| {
+            let sent = update_tx.send(update).is_ok();
+            ctx.request_repaint();
+            sent
+        };
+
+        let mut radio = match connect(&target) {
+            Ok((radio, description)) => {
+                send(Update::Connected(description));
+                radio
+            }
+            Err(e) => {
+                send(Update::Error(format!("connect: {e}")));
+                send(Update::Disconnected);
+                return;
+            }
+        };
+
+        loop {
+            loop {
+                let result = match req_rx.try_recv() {
+                    Ok(Request::SetFrequency(freq)) => radio.set_frequency(freq),
+                    Ok(Request::SetMode(mode)) => radio.set_mode(mode),
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => return,
+                };
+                if let Err(e) = result {
+                    send(Update::Error(e.to_string()));
+                }
+            }
+
+            let state = Update::State {
+                frequency: radio.read_frequency().ok(),
+                mode: radio.read_mode().ok(),
+                s_meter: radio.read_s_meter().ok(),
+            };
+            if !send(state) {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+
+    (req_tx, update_rx)
+}
+
+// ---------------------------------------------------------------------------
+// Window
+// ---------------------------------------------------------------------------
+
+struct Connection {
+    requests: mpsc::Sender<Request>,
+    updates: mpsc::Receiver<Update>,
+}
+
+struct Panel {
+    connection: Option<Connection>,
+    connected: bool,
+    status: String,
+
+    // Connect dialog
+    port_input: String,
+    baud_choice: Option<u32>,
+    use_sim: bool,
+
+    // Last polled state
+    frequency: Option<Frequency>,
+    mode: Option<OperatingMode>,
+    s_meter: Option<u16>,
+
+    freq_input: String,
+}
+
+impl Panel {
+    fn new(ctx: &egui::Context, target: Option<Target>) -> Self {
+        let mut panel = Self {
+            connection: None,
+            connected: false,
+            status: "Not connected".to_string(),
+            port_input: find_id52_port().unwrap_or_default(),
+            baud_choice: None,
+            use_sim: false,
+            frequency: None,
+            mode: None,
+            s_meter: None,
+            freq_input: String::new(),
+        };
+        if let Some(target) = target {
+            if let Target::Port { name, baud } = &target {
+                panel.port_input = name.clone();
+                panel.baud_choice = *baud;
+            }
+            panel.use_sim = matches!(target, Target::Sim);
+            panel.start(ctx, target);
+        }
+        panel
+    }
+
+    fn start(&mut self, ctx: &egui::Context, target: Target) {
+        let (requests, updates) = spawn_worker(target, ctx.clone());
+        self.connection = Some(Connection { requests, updates });
+        self.status = "Connecting...".to_string();
+    }
+
+    fn disconnect(&mut self) {
+        self.connection = None;
+        self.connected = false;
+        self.frequency = None;
+        self.mode = None;
+        self.s_meter = None;
+        self.status = "Not connected".to_string();
+    }
+
+    fn send(&mut self, request: Request) {
+        if let Some(conn) = &self.connection {
+            let _ = conn.requests.send(request);
+        }
+    }
+
+    fn drain_updates(&mut self) {
+        let Some(conn) = &self.connection else {
+            return;
+        };
+        let updates: Vec<Update> = conn.updates.try_iter().collect();
+        for update in updates {
+            match update {
+                Update::Connected(description) => {
+                    self.connected = true;
+                    self.status = format!("Connected: {description}");
+                }
+                Update::State {
+                    frequency,
+                    mode,
+                    s_meter,
+                } => {
+                    self.frequency = frequency;
+                    self.mode = mode;
+                    self.s_meter = s_meter;
+                }
+                Update::Error(msg) => self.status = msg,
+                Update::Disconnected => {
+                    let status = std::mem::take(&mut self.status);
+                    self.disconnect();
+                    self.status = status;
+                }
+            }
+        }
+    }
+
+    fn connect_dialog(&mut self, ctx: &egui::Context) {
+        let mut target = None;
+        egui::Window::new("Connect")
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.checkbox(&mut self.use_sim, "Simulator (no radio)");
+                ui.add_enabled_ui(!self.use_sim, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Port");
+                        ui.text_edit_singleline(&mut self.port_input);
+                    });
+                    egui::ComboBox::from_label("Baud")
+                        .selected_text(baud_label(self.baud_choice))
+                        .show_ui(ui, |ui| {
+                            for baud in BAUD_RATES {
+                                ui.selectable_value(&mut self.baud_choice, baud, baud_label(baud));
+                            }
+                        });
+                });
+                ui.label(&self.status);
+                if ui.button("Connect").clicked() {
+                    target = Some(if self.use_sim {
+                        Target::Sim
+                    } else {
+                        Target::Port {
+                            name: self.port_input.trim().to_string(),
+                            baud: self.baud_choice,
+                        }
+                    });
+                }
+            });
+        if let Some(target) = target {
+            self.start(ctx, target);
+        }
+    }
+
+    fn front_panel(&mut self, ui: &mut egui::Ui) {
+        let freq_text = self
+            .frequency
+            .map(|f| format!("{:.5} MHz", f.mhz()))
+            .unwrap_or_else(|| "---.----- MHz".to_string());
+        ui.heading(egui::RichText::new(freq_text).monospace().size(28.0));
+
+        ui.horizontal(|ui| {
+            let entry = ui.add(
+                egui::TextEdit::singleline(&mut self.freq_input)
+                    .hint_text("MHz")
+                    .desired_width(120.0),
+            );
+            let submitted = entry.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if ui.button("Set").clicked() || submitted {
+                match parse_mhz(&self.freq_input) {
+                    Ok(freq) => {
+                        self.send(Request::SetFrequency(freq));
+                        self.freq_input.clear();
+                    }
+                    Err(msg) => self.status = msg,
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            for mode in MODES {
+                if ui
+                    .selectable_label(self.mode == Some(mode), mode.to_string())
+                    .clicked()
+                {
+                    self.send(Request::SetMode(mode));
+                }
+            }
+        });
+
+        let raw = self.s_meter.unwrap_or(0);
+        ui.add(
+            egui::ProgressBar::new(raw as f32 / 255.0)
+                .text(format!("S-meter {raw}"))
+                .desired_width(ui.available_width()),
+        );
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(&self.status);
+            if ui.button("Disconnect").clicked() {
+                self.disconnect();
+            }
+        });
+    }
+}
+
+impl eframe::App for Panel {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.drain_updates();
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.add_enabled_ui(self.connected, |ui| self.front_panel(ui));
+        });
+        if self.connection.is_none() {
+            self.connect_dialog(ctx);
+        }
+    }
+}
+
+fn baud_label(baud: Option<u32>) -> String {
+    baud.map_or_else(|| "Auto".to_string(), |b| b.to_string())
+}
+
+fn parse_mhz(input: &str) -> Result<Frequency, String> {
+    let mhz: f64 = input
+        .trim()
+        .parse()
+        .map_err(|_| format!("not a frequency: {input:?}"))?;
+    Frequency::from_mhz(mhz).map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Simulator
+// ---------------------------------------------------------------------------
+
+/// In-process stand-in for the radio that answers the commands this panel
+/// sends and rejects everything else with NG.
+struct SimTransport {
+    frequency: Frequency,
+    mode: OperatingMode,
+    tick: u16,
+    pending: Vec<u8>,
+}
+
+impl Default for SimTransport {
+    fn default() -> Self {
+        Self {
+            frequency: Frequency::from_hz(146_520_000).expect("valid frequency"),
+            mode: OperatingMode::Fm,
+            tick: 0,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl SimTransport {
+    fn answer(&mut self, request: &Frame) -> Frame {
+        // The parser splits the first payload byte off as the sub-command.
+        let payload: Vec<u8> = request
+            .sub_command
+            .into_iter()
+            .chain(request.data.iter().copied())
+            .collect();
+
+        let (command, data) = match request.command {
+            cmd::READ_FREQ => match self.frequency.to_civ_bytes() {
+                Ok(bytes) => (cmd::READ_FREQ, bytes.to_vec()),
+                Err(_) => (NG, vec![]),
+            },
+            cmd::SET_FREQ => match <[u8; 5]>::try_from(payload.as_slice())
+                .ok()
+                .and_then(|bytes| Frequency::from_civ_bytes(bytes).ok())
+            {
+                Some(freq) => {
+                    self.frequency = freq;
+                    (OK, vec![])
+                }
+                None => (NG, vec![]),
+            },
+            cmd::READ_MODE => {
+                let (mode, filter) = self.mode.to_civ_bytes();
+                (cmd::READ_MODE, vec![mode, filter])
+            }
+            cmd::SET_MODE => match payload.as_slice() {
+                [mode, filter] => match OperatingMode::from_civ_bytes(*mode, *filter) {
+                    Ok(mode) => {
+                        self.mode = mode;
+                        (OK, vec![])
+                    }
+                    Err(_) => (NG, vec![]),
+                },
+                _ => (NG, vec![]),
+            },
+            cmd::METER => {
+                // A slow triangle wave so the bar visibly moves.
+                self.tick = (self.tick + 5) % 400;
+                let level = if self.tick < 200 {
+                    self.tick
+                } else {
+                    400 - self.tick
+                };
+                let mut data = payload;
+                data.extend(bcd::encode_bcd_be(level as u64, 2).unwrap_or_default());
+                (cmd::METER, data)
+            }
+            _ => (NG, vec![]),
+        };
+
+        Frame {
+            dst: ADDR_CONTROLLER,
+            src: ADDR_ID52,
+            ..Frame::new(command, None, data)
+        }
+    }
+}
+
+impl Transport for SimTransport {
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        if let Ok(Some((request, _))) = Frame::parse(buf) {
+            let reply = self.answer(&request);
+            self.pending.extend(reply.to_bytes());
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pending.is_empty() {
+            return Err(io::ErrorKind::TimedOut.into());
+        }
+        let n = buf.len().min(self.pending.len());
+        buf[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        Ok(n)
+    }
+
+    fn set_read_timeout(&mut self, _timeout: Duration) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sim_radio() -> Radio {
+        Radio::new(Box::new(SimTransport::default()), RadioConfig::default())
+    }
+
+    #[test]
+    fn test_sim_round_trips_frequency_and_mode() {
+        let mut radio = sim_radio();
+        assert_eq!(radio.read_frequency().unwrap().hz(), 146_520_000);
+        let freq = Frequency::from_hz(433_500_000).unwrap();
+        radio.set_frequency(freq).unwrap();
+        assert_eq!(radio.read_frequency().unwrap(), freq);
+
+        radio.set_mode(OperatingMode::Dv).unwrap();
+        assert_eq!(radio.read_mode().unwrap(), OperatingMode::Dv);
+    }
+
+    #[test]
+    fn test_sim_meter_moves_and_unknown_commands_are_rejected() {
+        let mut radio = sim_radio();
+        let first = radio.read_s_meter().unwrap();
+        assert_ne!(radio.read_s_meter().unwrap(), first);
+        assert!(matches!(
+            radio.read_gps_position(),
+            Err(civ_protocol::CivError::Ng)
+        ));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |s: &str| parse_args(s.split_whitespace().map(String::from));
+        assert!(args("").unwrap().is_none());
+        assert!(matches!(args("--sim").unwrap(), Some(Target::Sim)));
+        assert!(matches!(
+            args("--port /dev/ttyACM0 --baud 19200").unwrap(),
+            Some(Target::Port { name, baud: Some(19200) }) if name == "/dev/ttyACM0"
+        ));
+        assert!(args("--baud fast").is_err());
+        assert!(args("--bogus").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+#ifndef CIV_PROTOCOL_H
+#define CIV_PROTOCOL_H
+
+/* Generated by cbindgen from civ-protocol/src/ffi.rs — do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Status code returned by every FFI function.
+typedef enum CivStatus {
+  // Success.
+  CIV_STATUS_OK = 0,
+  // A required pointer argument was null.
+  CIV_STATUS_NULL_POINTER = -1,
+  // The output buffer is too small; `*out_len` holds the required size.
+  CIV_STATUS_BUFFER_TOO_SMALL = -2,
+  // The input does not contain a valid CI-V frame.
+  CIV_STATUS_INVALID_FRAME = -3,
+  // The input ends before the frame's end-of-message byte.
+  CIV_STATUS_INCOMPLETE = -4,
+  // The frequency is outside the encodable range.
+  CIV_STATUS_FREQUENCY_OUT_OF_RANGE = -5,
+  // An internal panic was caught at the boundary (a bug in this library).
+  CIV_STATUS_PANIC = -99,
+} CivStatus;
+
+// A parsed CI-V frame.
+//
+// `data` is owned by the library; release it with [`civ_frame_free`].
+typedef struct CivFrame {
+  // Destination address.
+  uint8_t dst;
+  // Source address.
+  uint8_t src;
+  // Command byte.
+  uint8_t command;
+  // Non-zero when `sub_command` is present.
+  uint8_t has_sub_command;
+  // Sub-command byte (only meaningful when `has_sub_command` is non-zero).
+  uint8_t sub_command;
+  // Payload bytes after the sub-command (null when `data_len` is 0).
+  uint8_t *data;
+  // Number of payload bytes at `data`.
+  size_t data_len;
+  // Bytes of the input consumed, including any garbage before the preamble.
+  size_t consumed;
+} CivFrame;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Encode a "set frequency" frame for `hz` into `out_buf`.
+//
+// # Safety
+//
+// `out_len` must point to a valid `size_t` holding the capacity of
+// `out_buf`, and `out_buf` must be valid for that many bytes of writes.
+enum CivStatus civ_encode_set_frequency(uint64_t hz, uint8_t *out_buf, size_t *out_len);
+
+// Parse the first CI-V frame in `bytes[..len]` into `*out`.
+//
+// Leading garbage before the preamble is skipped. On [`CivStatus::Ok`] the
+// frame's `data` must be released with [`civ_frame_free`].
+//
+// # Safety
+//
+// `bytes` must be valid for `len` bytes of reads and `out` must point to
+// writable memory for one `CivFrame`.
+enum CivStatus civ_parse_frame(const uint8_t *bytes, size_t len, struct CivFrame *out);
+
+// Release the payload owned by a frame filled in by [`civ_parse_frame`].
+//
+// The frame is zeroed afterwards, so calling this twice is harmless.
+// Passing null is a no-op.
+//
+// # Safety
+//
+// `frame` must be null or point to a `CivFrame` last written by
+// [`civ_parse_frame`] (or already freed by this function).
+void civ_frame_free(struct CivFrame *frame);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CIV_PROTOCOL_H */
@@ -0,0 +1,59 @@
+use std::ops::RangeInclusive;
+
+/// An amateur band the ID-52A Plus transmits on, all values in Hz.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Band {
+    /// Operator-facing name, e.g. "2 m".
+    pub name: &'static str,
+    /// Transmit band edges.
+    pub edges: RangeInclusive<u64>,
+    /// Sensible repeater offsets.
+    ///
+    /// Deliberately generous — only meant to catch obvious slips like a
+    /// 70 cm offset typed while on 2 m.
+    pub repeater_offsets: RangeInclusive<u64>,
+}
+
+/// Bands the ID-52A Plus transmits (and decodes DV) on.
+pub const TX_BANDS: &[Band] = &[
+    Band {
+        name: "2 m",
+        edges: 144_000_000..=148_000_000,
+        repeater_offsets: 100_000..=2_000_000,
+    },
+    Band {
+        name: "70 cm",
+        edges: 430_000_000..=450_000_000,
+        repeater_offsets: 1_000_000..=10_000_000,
+    },
+];
+
+/// Receive coverage of the ID-52A Plus, in Hz.
+pub const RX_COVERAGE: RangeInclusive<u64> = 495_000..=999_990_000;
+
+/// The transmit band containing `hz`, if any.
+pub fn tx_band(hz: u64) -> Option<&'static Band> {
+    TX_BANDS.iter().find(|band| band.edges.contains(&hz))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tx_band_lookup() {
+        assert_eq!(tx_band(146_520_000).map(|b| b.name), Some("2 m"));
+        assert_eq!(tx_band(430_000_000).map(|b| b.name), Some("70 cm"));
+        assert_eq!(tx_band(450_000_000).map(|b| b.name), Some("70 cm"));
+        assert!(tx_band(162_550_000).is_none());
+        assert!(tx_band(223_500_000).is_none());
+    }
+
+    #[test]
+    fn test_tx_bands_are_received() {
+        for band in TX_BANDS {
+            assert!(RX_COVERAGE.contains(band.edges.start()));
+            assert!(RX_COVERAGE.contains(band.edges.end()));
+        }
+    }
+}
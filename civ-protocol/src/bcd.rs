@@ -156,7 +156,7 @@ mod tests {
 
     #[test]
     fn test_roundtrip_le() {
-        for value in [0u64, 1, 100, 12345, 999_999_999_9] {
+        for value in [0u64, 1, 100, 12345, 9_999_999_999] {
             let encoded = encode_bcd_le(value, 5).unwrap();
             let decoded = decode_bcd_le(&encoded).unwrap();
             assert_eq!(decoded, value, "roundtrip failed for {value}");
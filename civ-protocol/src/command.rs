@@ -1,6 +1,7 @@
 use crate::bcd;
-use crate::error::Result;
+use crate::error::{CivError, Result};
 use crate::frequency::Frequency;
+use crate::gps;
 use crate::mode::OperatingMode;
 use crate::protocol::Frame;
 
@@ -22,6 +23,8 @@ pub mod cmd {
     pub const METER: u8 = 0x15;
     /// Read/write various function settings (tone squelch, etc.).
     pub const VARIOUS: u8 = 0x16;
+    /// Read/write menu settings and other extended data.
+    pub const SETTING: u8 = 0x1A;
     /// Send/read tone/DTCS frequency and code settings.
     pub const TONE: u8 = 0x1B;
     /// Read duplex offset frequency.
@@ -34,10 +37,20 @@ pub mod cmd {
     pub const POWER: u8 = 0x18;
     /// Read transceiver ID.
     pub const READ_ID: u8 = 0x19;
-    /// Read GPS position data (My Position).
+    /// Read GPS position data (My Position) and the GPS message.
     pub const READ_GPS: u8 = 0x23;
 }
 
+/// Direction values for the READ_DUPLEX (0x0F) command, sent as the sub-command.
+pub mod duplex_sub {
+    /// Simplex — transmit on the receive frequency.
+    pub const SIMPLEX: u8 = 0x10;
+    /// DUP- — transmit below the receive frequency by the offset.
+    pub const MINUS: u8 = 0x11;
+    /// DUP+ — transmit above the receive frequency by the offset.
+    pub const PLUS: u8 = 0x12;
+}
+
 /// Sub-commands for the LEVEL (0x14) command.
 pub mod level_sub {
     /// AF output level (volume).
@@ -56,6 +69,15 @@ pub mod various_sub {
     pub const TONE_SQUELCH_FUNC: u8 = 0x5D;
 }
 
+/// Sub-commands for the SETTING (0x1A) command.
+pub mod setting_sub {
+    /// Menu setting, followed by a 2-byte BCD item number (0000–9999).
+    pub const MENU: u8 = 0x05;
+}
+
+/// Highest menu item number that fits in the 2-byte BCD item field.
+pub const SETTING_ITEM_MAX: u16 = 9999;
+
 /// Sub-commands for the TONE (0x1B) command.
 pub mod tone_sub {
     /// Repeater tone (Tx) frequency — 3 bytes BCD.
@@ -93,6 +115,14 @@ pub mod power_sub {
     pub const ON: u8 = 0x01;
 }
 
+/// Sub-commands for the READ_GPS (0x23) command.
+pub mod gps_sub {
+    /// My Position — 27 bytes BCD.
+    pub const MY_POSITION: u8 = 0x00;
+    /// GPS message — 20 ASCII characters, space padded.
+    pub const MESSAGE: u8 = 0x02;
+}
+
 /// A CI-V command to send to the radio.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Command {
@@ -122,24 +152,39 @@ pub enum Command {
     ReadTransceiverId,
     /// Read a various function setting. The `u8` is the sub-command (e.g. 0x5D).
     ReadVarious(u8),
-    /// Read duplex direction (0x10=Simplex, 0x11=DUP-, 0x12=DUP+).
+    /// Read a various function setting with its whole value payload, for
+    /// sub-commands whose value is not a single byte or is not mapped yet.
+    ReadVariousRaw(u8),
+    /// Read duplex direction (see [`duplex_sub`]).
     ReadDuplex,
     /// Read duplex offset frequency (5-byte LE BCD, same as operating frequency).
     ReadOffset,
     /// Read a tone/DTCS setting. The `u8` is the sub-command (0x00=Tx tone, 0x01=Rx tone, 0x02=DTCS).
     ReadTone(u8),
-    /// Set duplex direction (0x10=Simplex, 0x11=DUP-, 0x12=DUP+).
+    /// Set duplex direction (see [`duplex_sub`]).
     SetDuplex(u8),
     /// Set duplex offset frequency (3-byte LE BCD, 100 Hz resolution).
     SetOffset(u64),
     /// Write a various function setting. (sub_command, value).
     SetVarious(u8, u8),
+    /// Write a various function setting as raw value bytes, sent as is.
+    /// (sub_command, value). The counterpart of [`Command::ReadVariousRaw`].
+    SetVariousRaw(u8, Vec<u8>),
     /// Write a tone frequency. (sub_command 0x00=Tx or 0x01=Rx, freq in tenths of Hz).
     SetTone(u8, u16),
     /// Write DTCS code and polarity. (tx_pol, rx_pol, code).
     SetDtcs(u8, u8, u16),
     /// Read GPS position data (command 0x23, sub 0x00).
     ReadGpsPosition,
+    /// Read the GPS message (command 0x23, sub 0x02).
+    ReadGpsMessage,
+    /// Write the GPS message. Encoding fails if the text is too long or
+    /// contains characters the radio cannot display.
+    SetGpsMessage(String),
+    /// Read a menu setting (command 0x1A, sub 0x05). The `u16` is the item number (0–9999).
+    ReadSetting(u16),
+    /// Write a menu setting. (item number, raw value bytes). The value is sent as is.
+    SetSetting(u16, Vec<u8>),
 }
 
 impl Command {
@@ -167,7 +212,9 @@ impl Command {
             Command::PowerOn => Frame::new(cmd::POWER, Some(power_sub::ON), vec![]),
             Command::PowerOff => Frame::new(cmd::POWER, Some(power_sub::OFF), vec![]),
             Command::ReadTransceiverId => Frame::new(cmd::READ_ID, Some(0x00), vec![]),
-            Command::ReadVarious(sub) => Frame::new(cmd::VARIOUS, Some(*sub), vec![]),
+            Command::ReadVarious(sub) | Command::ReadVariousRaw(sub) => {
+                Frame::new(cmd::VARIOUS, Some(*sub), vec![])
+            }
             Command::ReadDuplex => Frame::new(cmd::READ_DUPLEX, None, vec![]),
             Command::ReadOffset => Frame::new(cmd::READ_OFFSET, None, vec![]),
             Command::ReadTone(sub) => Frame::new(cmd::TONE, Some(*sub), vec![]),
@@ -179,6 +226,9 @@ impl Command {
                 Frame::new(cmd::SET_OFFSET, None, data)
             }
             Command::SetVarious(sub, value) => Frame::new(cmd::VARIOUS, Some(*sub), vec![*value]),
+            Command::SetVariousRaw(sub, value) => {
+                Frame::new(cmd::VARIOUS, Some(*sub), value.clone())
+            }
             Command::SetTone(sub, freq_tenths) => {
                 // Encode tone frequency as 3 bytes: [0x00, hundreds_tens_BCD, units_tenths_BCD]
                 let ht = (*freq_tenths / 100) as u8;
@@ -187,7 +237,22 @@ impl Command {
                 let ut_bcd = ((ut / 10) << 4) | (ut % 10);
                 Frame::new(cmd::TONE, Some(*sub), vec![0x00, ht_bcd, ut_bcd])
             }
-            Command::ReadGpsPosition => Frame::new(cmd::READ_GPS, Some(0x00), vec![]),
+            Command::ReadGpsPosition => {
+                Frame::new(cmd::READ_GPS, Some(gps_sub::MY_POSITION), vec![])
+            }
+            Command::ReadGpsMessage => Frame::new(cmd::READ_GPS, Some(gps_sub::MESSAGE), vec![]),
+            Command::SetGpsMessage(text) => {
+                let data = gps::encode_gps_message(text)?;
+                Frame::new(cmd::READ_GPS, Some(gps_sub::MESSAGE), data.to_vec())
+            }
+            Command::ReadSetting(item) => {
+                Frame::new(cmd::SETTING, Some(setting_sub::MENU), setting_item(*item)?)
+            }
+            Command::SetSetting(item, value) => {
+                let mut data = setting_item(*item)?;
+                data.extend_from_slice(value);
+                Frame::new(cmd::SETTING, Some(setting_sub::MENU), data)
+            }
             Command::SetDtcs(tx_pol, rx_pol, code) => {
                 // Encode DTCS as 3 bytes: [polarity_nibbles, first_digit_BCD, second_third_BCD]
                 let polarity = (tx_pol << 4) | (rx_pol & 0x0F);
@@ -217,12 +282,18 @@ impl Command {
             Command::ReadMeter(_) => cmd::METER,
             Command::PowerOn | Command::PowerOff => cmd::POWER,
             Command::ReadTransceiverId => cmd::READ_ID,
-            Command::ReadVarious(_) | Command::SetVarious(_, _) => cmd::VARIOUS,
+            Command::ReadVarious(_)
+            | Command::ReadVariousRaw(_)
+            | Command::SetVarious(_, _)
+            | Command::SetVariousRaw(_, _) => cmd::VARIOUS,
             Command::ReadDuplex | Command::SetDuplex(_) => cmd::READ_DUPLEX,
             Command::ReadOffset => cmd::READ_OFFSET,
             Command::SetOffset(_) => cmd::SET_OFFSET,
             Command::ReadTone(_) | Command::SetTone(_, _) | Command::SetDtcs(_, _, _) => cmd::TONE,
-            Command::ReadGpsPosition => cmd::READ_GPS,
+            Command::ReadGpsPosition | Command::ReadGpsMessage | Command::SetGpsMessage(_) => {
+                cmd::READ_GPS
+            }
+            Command::ReadSetting(_) | Command::SetSetting(_, _) => cmd::SETTING,
         }
     }
 
@@ -240,17 +311,30 @@ impl Command {
             Command::PowerOn => Some(power_sub::ON),
             Command::PowerOff => Some(power_sub::OFF),
             Command::ReadTransceiverId => Some(0x00),
-            Command::ReadVarious(sub) | Command::SetVarious(sub, _) => Some(*sub),
+            Command::ReadVarious(sub)
+            | Command::ReadVariousRaw(sub)
+            | Command::SetVarious(sub, _)
+            | Command::SetVariousRaw(sub, _) => Some(*sub),
             Command::ReadDuplex => None,
             Command::SetDuplex(dir) => Some(*dir),
             Command::ReadOffset | Command::SetOffset(_) => None,
             Command::ReadTone(sub) | Command::SetTone(sub, _) => Some(*sub),
             Command::SetDtcs(_, _, _) => Some(tone_sub::DTCS),
-            Command::ReadGpsPosition => Some(0x00),
+            Command::ReadGpsPosition => Some(gps_sub::MY_POSITION),
+            Command::ReadGpsMessage | Command::SetGpsMessage(_) => Some(gps_sub::MESSAGE),
+            Command::ReadSetting(_) | Command::SetSetting(_, _) => Some(setting_sub::MENU),
         }
     }
 }
 
+/// Encode a menu item number as 2 bytes of big-endian BCD (item 123 → `[0x01, 0x23]`).
+fn setting_item(item: u16) -> Result<Vec<u8>> {
+    if item > SETTING_ITEM_MAX {
+        return Err(CivError::SettingItemOutOfRange(item));
+    }
+    bcd::encode_bcd_be(item as u64, 2)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -346,4 +430,72 @@ mod tests {
         );
         assert_eq!(Command::SetMode(OperatingMode::Fm).command_byte(), 0x06);
     }
+
+    #[test]
+    fn test_read_gps_message() {
+        let frame = Command::ReadGpsMessage.to_frame().unwrap();
+        let bytes = frame.to_bytes();
+        assert_eq!(bytes, vec![0xFE, 0xFE, 0xB4, 0xE0, 0x23, 0x02, 0xFD]);
+    }
+
+    #[test]
+    fn test_set_gps_message_pads_to_width() {
+        let frame = Command::SetGpsMessage("QRV 2m".into()).to_frame().unwrap();
+        let bytes = frame.to_bytes();
+        assert_eq!(&bytes[..6], &[0xFE, 0xFE, 0xB4, 0xE0, 0x23, 0x02]);
+        assert_eq!(&bytes[6..26], b"QRV 2m              ");
+        assert_eq!(bytes[26], 0xFD);
+    }
+
+    #[test]
+    fn test_set_gps_message_rejects_bad_text() {
+        assert!(
+            Command::SetGpsMessage("caf\u{e9}".into())
+                .to_frame()
+                .is_err()
+        );
+        assert!(Command::SetGpsMessage("x".repeat(21)).to_frame().is_err());
+    }
+
+    #[test]
+    fn test_set_various_raw() {
+        let frame = Command::SetVariousRaw(0x5D, vec![0x00, 0x42])
+            .to_frame()
+            .unwrap();
+        let bytes = frame.to_bytes();
+        assert_eq!(
+            bytes,
+            vec![0xFE, 0xFE, 0xB4, 0xE0, 0x16, 0x5D, 0x00, 0x42, 0xFD]
+        );
+    }
+
+    #[test]
+    fn test_read_setting() {
+        let frame = Command::ReadSetting(123).to_frame().unwrap();
+        let bytes = frame.to_bytes();
+        assert_eq!(
+            bytes,
+            vec![0xFE, 0xFE, 0xB4, 0xE0, 0x1A, 0x05, 0x01, 0x23, 0xFD]
+        );
+    }
+
+    #[test]
+    fn test_set_setting() {
+        let frame = Command::SetSetting(7, vec![0x01, 0xA0]).to_frame().unwrap();
+        let bytes = frame.to_bytes();
+        assert_eq!(
+            bytes,
+            vec![
+                0xFE, 0xFE, 0xB4, 0xE0, 0x1A, 0x05, 0x00, 0x07, 0x01, 0xA0, 0xFD
+            ]
+        );
+    }
+
+    #[test]
+    fn test_setting_item_out_of_range() {
+        assert!(matches!(
+            Command::ReadSetting(10_000).to_frame(),
+            Err(CivError::SettingItemOutOfRange(10_000))
+        ));
+    }
 }
@@ -14,8 +14,8 @@ pub enum CivError {
     #[error("ID-52A Plus serial port not found")]
     PortNotFound,
 
-    #[error("invalid CI-V frame")]
-    InvalidFrame,
+    #[error("invalid CI-V frame: {0}")]
+    InvalidFrame(FrameError),
 
     #[error("radio returned NG (command rejected)")]
     Ng,
@@ -31,4 +31,87 @@ pub enum CivError {
 
     #[error("unknown operating mode: {0:#04x}")]
     UnknownMode(u8),
+
+    #[error("unsupported character {ch:?} at position {position} in GPS message")]
+    UnsupportedGpsMessageChar { ch: char, position: usize },
+
+    #[error("GPS message is {len} characters long, maximum is {max}")]
+    GpsMessageTooLong { len: usize, max: usize },
+
+    #[error("setting item {0} out of range (0000–9999)")]
+    SettingItemOutOfRange(u16),
+}
+
+/// Why a frame was rejected as invalid.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameError {
+    #[error("frame is {0} bytes, shorter than the 6-byte minimum")]
+    TooShort(usize),
+
+    #[error("expected {expected} data byte(s), got {actual}")]
+    Length { expected: usize, actual: usize },
+
+    #[error("expected at least {min} data byte(s), got {actual}")]
+    ShortData { min: usize, actual: usize },
+
+    #[error("missing sub-command")]
+    MissingSubCommand,
+
+    #[error("expected sub-command {expected:#04x}, got {actual:#04x}")]
+    SubCommand { expected: u8, actual: u8 },
+
+    #[error("unknown sub-command {0:#04x}")]
+    UnknownSubCommand(u8),
+
+    #[error("unexpected command {0:#04x}")]
+    UnexpectedCommand(u8),
+
+    #[error("expected menu item {expected:04}, got {actual:04}")]
+    SettingItem { expected: u16, actual: u16 },
+
+    #[error("non-printable byte {0:#04x} in text")]
+    NonPrintable(u8),
+
+    /// The radio answered with a response of the wrong kind. Holds the name
+    /// of the request, e.g. `"ReadFrequency"`.
+    #[error("unexpected response to {0}")]
+    UnexpectedResponse(
+        #[cfg_attr(
+            feature = "serde",
+            serde(deserialize_with = "deserialize_request_name")
+        )]
+        RequestName,
+    ),
+}
+
+/// Name of the request in [`FrameError::UnexpectedResponse`], e.g.
+/// `"ReadFrequency"`.
+///
+/// An alias rather than a bare `&'static str` so serde does not borrow the
+/// field from its input; see `deserialize_request_name`.
+pub type RequestName = &'static str;
+
+/// Deserialize a request name for [`FrameError::UnexpectedResponse`].
+///
+/// Only recorded sessions are deserialized, and the names come from a short,
+/// fixed list, so each distinct name is leaked once and reused after that.
+#[cfg(feature = "serde")]
+fn deserialize_request_name<'de, D>(deserializer: D) -> std::result::Result<RequestName, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use std::collections::HashSet;
+    use std::sync::{Mutex, OnceLock};
+
+    static NAMES: OnceLock<Mutex<HashSet<RequestName>>> = OnceLock::new();
+
+    let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+    let mut names = NAMES.get_or_init(Default::default).lock().unwrap();
+    if let Some(known) = names.get(name.as_str()) {
+        return Ok(known);
+    }
+    let leaked: RequestName = Box::leak(name.into_boxed_str());
+    names.insert(leaked);
+    Ok(leaked)
 }
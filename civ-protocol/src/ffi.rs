@@ -0,0 +1,249 @@
+//! Minimal C ABI for the frame encoder/parser (enabled with the `ffi` feature).
+//!
+//! Only the pure encode/parse core is exposed — no serial handling. Every
+//! function returns a [`CivStatus`] and never unwinds across the boundary:
+//! null pointers are reported as [`CivStatus::NullPointer`] and any internal
+//! panic is caught and reported as [`CivStatus::Panic`].
+//!
+//! # Memory ownership
+//!
+//! - Encode functions write into a caller-owned buffer. `*out_len` holds the
+//!   buffer capacity on entry and the number of bytes written on return. If
+//!   the buffer is too small nothing is written, `*out_len` is set to the
+//!   required size and [`CivStatus::BufferTooSmall`] is returned.
+//! - [`civ_parse_frame`] fills a caller-owned [`CivFrame`]. On success its
+//!   `data` pointer is allocated by this library and must be released with
+//!   [`civ_frame_free`] — never with the caller's `free()`. On any other
+//!   status the frame is left zeroed and owns nothing.
+//!
+//! The shared library is built on demand, so plain dependents of the crate
+//! don't link one:
+//!
+//! ```text
+//! cargo rustc -p civ-protocol --release --no-default-features --features ffi \
+//!     --crate-type cdylib
+//! ```
+//!
+//! The C header is generated with cbindgen:
+//!
+//! ```text
+//! cbindgen --config civ-protocol/cbindgen.toml --crate civ-protocol \
+//!     --output civ-protocol/include/civ_protocol.h
+//! ```
+
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::command::Command;
+use crate::error::CivError;
+use crate::frequency::Frequency;
+use crate::protocol::{Frame, PREAMBLE};
+
+/// Status code returned by every FFI function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CivStatus {
+    /// Success.
+    Ok = 0,
+    /// A required pointer argument was null.
+    NullPointer = -1,
+    /// The output buffer is too small; `*out_len` holds the required size.
+    BufferTooSmall = -2,
+    /// The input does not contain a valid CI-V frame.
+    InvalidFrame = -3,
+    /// The input ends before the frame's end-of-message byte.
+    Incomplete = -4,
+    /// The frequency is outside the encodable range.
+    FrequencyOutOfRange = -5,
+    /// An internal panic was caught at the boundary (a bug in this library).
+    Panic = -99,
+}
+
+impl From<CivError> for CivStatus {
+    fn from(e: CivError) -> Self {
+        match e {
+            CivError::FrequencyOutOfRange(_) => Self::FrequencyOutOfRange,
+            _ => Self::InvalidFrame,
+        }
+    }
+}
+
+/// A parsed CI-V frame.
+///
+/// `data` is owned by the library; release it with [`civ_frame_free`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct CivFrame {
+    /// Destination address.
+    pub dst: u8,
+    /// Source address.
+    pub src: u8,
+    /// Command byte.
+    pub command: u8,
+    /// Non-zero when `sub_command` is present.
+    pub has_sub_command: u8,
+    /// Sub-command byte (only meaningful when `has_sub_command` is non-zero).
+    pub sub_command: u8,
+    /// Payload bytes after the sub-command (null when `data_len` is 0).
+    pub data: *mut u8,
+    /// Number of payload bytes at `data`.
+    pub data_len: usize,
+    /// Bytes of the input consumed, including any garbage before the preamble.
+    pub consumed: usize,
+}
+
+impl CivFrame {
+    fn zeroed() -> Self {
+        Self {
+            dst: 0,
+            src: 0,
+            command: 0,
+            has_sub_command: 0,
+            sub_command: 0,
+            data: ptr::null_mut(),
+            data_len: 0,
+            consumed: 0,
+        }
+    }
+}
+
+/// Run `f`, turning a panic into [`CivStatus::Panic`].
+fn guard(f: impl FnOnce() -> CivStatus) -> CivStatus {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or(CivStatus::Panic)
+}
+
+/// Copy `bytes` into the caller's buffer following the `out_len` convention.
+///
+/// # Safety
+///
+/// `out_buf` must be valid for `*out_len` bytes of writes.
+unsafe fn write_out(bytes: &[u8], out_buf: *mut u8, out_len: *mut usize) -> CivStatus {
+    // SAFETY: the caller checked `out_len` for null.
+    let capacity = unsafe { *out_len };
+    // SAFETY: see above.
+    unsafe { *out_len = bytes.len() };
+    if capacity < bytes.len() {
+        return CivStatus::BufferTooSmall;
+    }
+    // SAFETY: the caller guarantees `out_buf` holds `capacity` bytes.
+    unsafe { ptr::copy_nonoverlapping(bytes.as_ptr(), out_buf, bytes.len()) };
+    CivStatus::Ok
+}
+
+/// Encode a "set frequency" frame for `hz` into `out_buf`.
+///
+/// # Safety
+///
+/// `out_len` must point to a valid `size_t` holding the capacity of
+/// `out_buf`, and `out_buf` must be valid for that many bytes of writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn civ_encode_set_frequency(
+    hz: u64,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> CivStatus {
+    guard(|| {
+        if out_buf.is_null() || out_len.is_null() {
+            return CivStatus::NullPointer;
+        }
+        let frame = match Frequency::from_hz(hz).and_then(|f| Command::SetFrequency(f).to_frame()) {
+            Ok(frame) => frame,
+            Err(e) => return e.into(),
+        };
+        // SAFETY: pointers checked above; validity is the caller's contract.
+        unsafe { write_out(&frame.to_bytes(), out_buf, out_len) }
+    })
+}
+
+/// Parse the first CI-V frame in `bytes[..len]` into `*out`.
+///
+/// Leading garbage before the preamble is skipped. On [`CivStatus::Ok`] the
+/// frame's `data` must be released with [`civ_frame_free`].
+///
+/// # Safety
+///
+/// `bytes` must be valid for `len` bytes of reads and `out` must point to
+/// writable memory for one `CivFrame`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn civ_parse_frame(
+    bytes: *const u8,
+    len: usize,
+    out: *mut CivFrame,
+) -> CivStatus {
+    guard(|| {
+        if bytes.is_null() || out.is_null() {
+            return CivStatus::NullPointer;
+        }
+        // SAFETY: `out` is non-null and writable per the caller's contract.
+        unsafe { out.write(CivFrame::zeroed()) };
+        // SAFETY: `bytes` is non-null and readable for `len` bytes.
+        let input = unsafe { std::slice::from_raw_parts(bytes, len) };
+
+        let (frame, consumed) = match Frame::parse(input) {
+            Ok(Some(parsed)) => parsed,
+            Ok(None) => return CivStatus::Incomplete,
+            Err(e) => return e.into(),
+        };
+        let Frame {
+            dst,
+            src,
+            command,
+            sub_command,
+            data,
+        } = frame;
+        let data_len = data.len();
+        let data = if data.is_empty() {
+            ptr::null_mut()
+        } else {
+            Box::into_raw(data.into_boxed_slice()).cast::<u8>()
+        };
+
+        // SAFETY: see above.
+        unsafe {
+            out.write(CivFrame {
+                dst,
+                src,
+                command,
+                has_sub_command: sub_command.is_some() as u8,
+                sub_command: sub_command.unwrap_or(0),
+                data,
+                data_len,
+                // `Frame::parse` counts from the preamble; report the offset
+                // into the caller's buffer instead.
+                consumed: consumed
+                    + input
+                        .windows(2)
+                        .position(|w| w == [PREAMBLE; 2])
+                        .unwrap_or(0),
+            })
+        };
+        CivStatus::Ok
+    })
+}
+
+/// Release the payload owned by a frame filled in by [`civ_parse_frame`].
+///
+/// The frame is zeroed afterwards, so calling this twice is harmless.
+/// Passing null is a no-op.
+///
+/// # Safety
+///
+/// `frame` must be null or point to a `CivFrame` last written by
+/// [`civ_parse_frame`] (or already freed by this function).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn civ_frame_free(frame: *mut CivFrame) {
+    let _ = guard(|| {
+        if frame.is_null() {
+            return CivStatus::NullPointer;
+        }
+        // SAFETY: non-null and valid per the caller's contract.
+        let frame = unsafe { &mut *frame };
+        if !frame.data.is_null() {
+            let slice = ptr::slice_from_raw_parts_mut(frame.data, frame.data_len);
+            // SAFETY: `data`/`data_len` came from `Box::into_raw` in `civ_parse_frame`.
+            drop(unsafe { Box::from_raw(slice) });
+        }
+        *frame = CivFrame::zeroed();
+        CivStatus::Ok
+    });
+}
@@ -8,6 +8,7 @@ use crate::error::{CivError, Result};
 /// CI-V encodes frequencies as 5 BCD bytes in little-endian order,
 /// giving 10 decimal digits with 1 Hz resolution.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Frequency(u64);
 
 impl Frequency {
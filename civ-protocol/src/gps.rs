@@ -1,7 +1,12 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::error::{CivError, FrameError, Result};
 use crate::response::RawGpsPosition;
 
 /// GPS position data from the radio's built-in receiver.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GpsPosition {
     /// Latitude in decimal degrees (negative = South).
     pub latitude: f64,
@@ -63,3 +68,510 @@ pub fn raw_to_gps_position(raw: &RawGpsPosition) -> GpsPosition {
         utc_second: raw.utc_second,
     }
 }
+
+/// Mean Earth radius in meters (for haversine distances).
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Great-circle distance between two positions in meters.
+pub fn distance_m(a: &GpsPosition, b: &GpsPosition) -> f64 {
+    let (lat1, lat2) = (a.latitude.to_radians(), b.latitude.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (b.longitude - a.longitude).to_radians();
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_M * h.sqrt().asin()
+}
+
+/// Width of the GPS message field in characters.
+pub const GPS_MESSAGE_LEN: usize = 20;
+
+/// Check that `text` fits the radio's GPS message field.
+///
+/// Only printable ASCII (space through `~`) is accepted. Character errors
+/// are reported before length errors, with 1-based positions, so a user
+/// fixing a long message with a stray symbol sees the symbol first.
+pub fn validate_gps_message(text: &str) -> Result<()> {
+    if let Some((i, ch)) = text
+        .chars()
+        .enumerate()
+        .find(|(_, c)| !matches!(c, ' '..='~'))
+    {
+        return Err(CivError::UnsupportedGpsMessageChar {
+            ch,
+            position: i + 1,
+        });
+    }
+    if text.len() > GPS_MESSAGE_LEN {
+        return Err(CivError::GpsMessageTooLong {
+            len: text.len(),
+            max: GPS_MESSAGE_LEN,
+        });
+    }
+    Ok(())
+}
+
+/// Encode `text` as the fixed-width, space-padded GPS message payload.
+pub fn encode_gps_message(text: &str) -> Result<[u8; GPS_MESSAGE_LEN]> {
+    validate_gps_message(text)?;
+    let mut data = [b' '; GPS_MESSAGE_LEN];
+    data[..text.len()].copy_from_slice(text.as_bytes());
+    Ok(data)
+}
+
+/// Decode a GPS message payload, dropping trailing space/NUL padding.
+///
+/// Trailing spaces the user typed are indistinguishable from padding and
+/// are dropped too.
+pub fn decode_gps_message(data: &[u8]) -> Result<String> {
+    let end = data
+        .iter()
+        .rposition(|&b| b != b' ' && b != 0x00)
+        .map_or(0, |i| i + 1);
+    let text = &data[..end];
+    if let Some(&b) = text.iter().find(|b| !(b' '..=b'~').contains(*b)) {
+        return Err(CivError::InvalidFrame(FrameError::NonPrintable(b)));
+    }
+    Ok(text.iter().map(|&b| b as char).collect())
+}
+
+/// A change worth telling the operator about, reported by [`GpsTracker`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GpsEvent {
+    /// Moved at least the configured distance since the last `Moved` (or
+    /// since the fix was acquired).
+    Moved { distance_m: f64 },
+    /// Speed rose above the start threshold.
+    StartedMoving,
+    /// Speed fell below the stop threshold.
+    Stopped,
+    /// No position for `confirm_samples` consecutive updates.
+    FixLost,
+    /// A position arrived while there was no fix.
+    FixAcquired,
+}
+
+impl fmt::Display for GpsEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Moved { distance_m } => write!(f, "moved {distance_m:.0} m"),
+            Self::StartedMoving => write!(f, "started moving"),
+            Self::Stopped => write!(f, "stopped"),
+            Self::FixLost => write!(f, "fix lost"),
+            Self::FixAcquired => write!(f, "fix acquired"),
+        }
+    }
+}
+
+/// Thresholds for [`GpsTracker`].
+///
+/// The start and stop speeds form a hysteresis band: walking pace sits
+/// between them, so it never toggles the moving state by itself.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsThresholds {
+    /// Distance that triggers [`GpsEvent::Moved`], in meters.
+    pub move_distance_m: f64,
+    /// Speed at or above which we are moving, in km/h.
+    pub start_speed_kmh: f64,
+    /// Speed at or below which we are stopped, in km/h.
+    pub stop_speed_kmh: f64,
+    /// How long a state change (start/stop moving, fix lost) must hold
+    /// before it is reported, measured between update timestamps.
+    ///
+    /// The radio's receiver updates about once a second, but it is usually
+    /// polled several times a second, so one odd reading arrives as a run of
+    /// identical samples. A time span, unlike a sample count, ignores it
+    /// however fast the poll runs.
+    pub confirm: Duration,
+}
+
+impl Default for GpsThresholds {
+    fn default() -> Self {
+        Self {
+            move_distance_m: 100.0,
+            start_speed_kmh: 8.0,
+            stop_speed_kmh: 3.0,
+            confirm: Duration::from_secs(3),
+        }
+    }
+}
+
+/// Turns a stream of GPS positions into [`GpsEvent`]s.
+///
+/// Feed it every poll result with [`update`](Self::update), stamped with
+/// when it was taken; `None` means the read failed or there was no fix.
+#[derive(Debug, Clone)]
+pub struct GpsTracker {
+    thresholds: GpsThresholds,
+    /// Position of the last `Moved` (or of the first fix).
+    anchor: Option<GpsPosition>,
+    has_fix: bool,
+    moving: bool,
+    /// Since when the speed has been on the far side of the current state's
+    /// threshold.
+    crossed_since: Option<Instant>,
+    /// Since when updates have had no position.
+    missing_since: Option<Instant>,
+}
+
+impl GpsTracker {
+    /// Create a tracker with the given thresholds. Starts with no fix, stopped.
+    pub fn new(thresholds: GpsThresholds) -> Self {
+        Self {
+            thresholds,
+            anchor: None,
+            has_fix: false,
+            moving: false,
+            crossed_since: None,
+            missing_since: None,
+        }
+    }
+
+    /// The thresholds in use.
+    pub fn thresholds(&self) -> GpsThresholds {
+        self.thresholds
+    }
+
+    /// True once a start has been confirmed and no stop since.
+    pub fn is_moving(&self) -> bool {
+        self.moving
+    }
+
+    /// Feed the poll result taken at `now` and return the events it caused.
+    pub fn update(&mut self, now: Instant, position: Option<&GpsPosition>) -> Vec<GpsEvent> {
+        let mut events = Vec::new();
+
+        let Some(position) = position else {
+            let since = *self.missing_since.get_or_insert(now);
+            if self.has_fix && now.duration_since(since) >= self.thresholds.confirm {
+                self.has_fix = false;
+                self.crossed_since = None;
+                events.push(GpsEvent::FixLost);
+            }
+            return events;
+        };
+        self.missing_since = None;
+
+        if !self.has_fix {
+            self.has_fix = true;
+            events.push(GpsEvent::FixAcquired);
+        }
+
+        // Distance is measured from the last reported point, not the previous
+        // sample, so slow drift still adds up to a `Moved` eventually.
+        match &self.anchor {
+            Some(anchor) => {
+                let distance_m = distance_m(anchor, position);
+                if distance_m >= self.thresholds.move_distance_m {
+                    events.push(GpsEvent::Moved { distance_m });
+                    self.anchor = Some(position.clone());
+                }
+            }
+            None => self.anchor = Some(position.clone()),
+        }
+
+        let crossed = if self.moving {
+            position.speed <= self.thresholds.stop_speed_kmh
+        } else {
+            position.speed >= self.thresholds.start_speed_kmh
+        };
+        if crossed {
+            let since = *self.crossed_since.get_or_insert(now);
+            if now.duration_since(since) >= self.thresholds.confirm {
+                self.moving = !self.moving;
+                self.crossed_since = None;
+                events.push(if self.moving {
+                    GpsEvent::StartedMoving
+                } else {
+                    GpsEvent::Stopped
+                });
+            }
+        } else {
+            self.crossed_since = None;
+        }
+
+        events
+    }
+}
+
+impl Default for GpsTracker {
+    fn default() -> Self {
+        Self::new(GpsThresholds::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Meters per degree of latitude (close enough for short synthetic tracks).
+    const M_PER_DEG_LAT: f64 = 111_195.0;
+
+    /// A position `north_m` meters north of a fixed origin, moving at `speed`.
+    fn at(north_m: f64, speed: f64) -> GpsPosition {
+        GpsPosition {
+            latitude: 40.0 + north_m / M_PER_DEG_LAT,
+            longitude: -74.0,
+            speed,
+            ..GpsPosition::default()
+        }
+    }
+
+    /// How often the TUI polls the radio.
+    const POLL: Duration = Duration::from_millis(200);
+
+    /// Feed a track sampled every `interval` and collect every event, tagged
+    /// with its sample index.
+    fn run_every(
+        tracker: &mut GpsTracker,
+        interval: Duration,
+        track: &[Option<GpsPosition>],
+    ) -> Vec<(usize, GpsEvent)> {
+        let start = Instant::now();
+        track
+            .iter()
+            .enumerate()
+            .flat_map(|(i, p)| {
+                let now = start + interval * i as u32;
+                tracker
+                    .update(now, p.as_ref())
+                    .into_iter()
+                    .map(move |e| (i, e))
+            })
+            .collect()
+    }
+
+    /// Feed a track with one sample per second, the receiver's update rate.
+    fn run(tracker: &mut GpsTracker, track: &[Option<GpsPosition>]) -> Vec<(usize, GpsEvent)> {
+        run_every(tracker, Duration::from_secs(1), track)
+    }
+
+    fn without_moves(events: Vec<(usize, GpsEvent)>) -> Vec<(usize, GpsEvent)> {
+        events
+            .into_iter()
+            .filter(|(_, e)| !matches!(e, GpsEvent::Moved { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn test_distance_m() {
+        let d = distance_m(&at(0.0, 0.0), &at(1000.0, 0.0));
+        assert!((d - 1000.0).abs() < 1.0, "{d}");
+        assert_eq!(distance_m(&at(5.0, 0.0), &at(5.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_first_fix_is_acquired_only() {
+        let mut tracker = GpsTracker::default();
+        let now = Instant::now();
+        assert_eq!(
+            tracker.update(now, Some(&at(0.0, 0.0))),
+            [GpsEvent::FixAcquired]
+        );
+        assert!(tracker.update(now + POLL, Some(&at(10.0, 0.0))).is_empty());
+    }
+
+    #[test]
+    fn test_moved_measures_from_last_report() {
+        let mut tracker = GpsTracker::default();
+        // Creep north 30 m per sample: no single step reaches 100 m.
+        let track: Vec<_> = (0..=8).map(|i| Some(at(i as f64 * 30.0, 0.0))).collect();
+        let moves: Vec<_> = run(&mut tracker, &track)
+            .into_iter()
+            .filter_map(|(i, e)| match e {
+                GpsEvent::Moved { distance_m } => Some((i, distance_m.round())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(moves, [(4, 120.0), (8, 120.0)]);
+    }
+
+    #[test]
+    fn test_start_and_stop_need_confirmation() {
+        let mut tracker = GpsTracker::default();
+        let speeds = [0.0, 20.0, 30.0, 30.0, 30.0, 0.0, 0.0, 0.0, 0.0];
+        let track: Vec<_> = speeds.iter().map(|&s| Some(at(0.0, s))).collect();
+        assert_eq!(
+            run(&mut tracker, &track),
+            [
+                (0, GpsEvent::FixAcquired),
+                (4, GpsEvent::StartedMoving),
+                (8, GpsEvent::Stopped),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_walking_pace_does_not_flap() {
+        let mut tracker = GpsTracker::default();
+        // Walking at 4–7 km/h with GPS jitter stays inside the 3–8 km/h band.
+        let speeds = [5.0, 4.1, 6.8, 3.4, 7.9, 5.5, 4.0, 6.2, 3.1, 7.0, 5.0, 4.4];
+        let track: Vec<_> = speeds.iter().map(|&s| Some(at(0.0, s))).collect();
+        assert_eq!(run(&mut tracker, &track), [(0, GpsEvent::FixAcquired)]);
+        assert!(!tracker.is_moving());
+    }
+
+    #[test]
+    fn test_single_spikes_are_ignored() {
+        let mut tracker = GpsTracker::default();
+        // Parked with isolated spikes, then driving with isolated dips.
+        let speeds = [
+            0.0, 25.0, 0.0, 0.0, 25.0, 25.0, 0.0, // parked
+            40.0, 40.0, 40.0, 40.0, 0.0, 40.0, 1.0, 1.0, 40.0, // driving
+        ];
+        let track: Vec<_> = speeds.iter().map(|&s| Some(at(0.0, s))).collect();
+        assert_eq!(
+            without_moves(run(&mut tracker, &track)),
+            [(0, GpsEvent::FixAcquired), (10, GpsEvent::StartedMoving)]
+        );
+        assert!(tracker.is_moving());
+    }
+
+    #[test]
+    fn test_fix_lost_after_consecutive_misses() {
+        let mut tracker = GpsTracker::default();
+        let p = Some(at(0.0, 0.0));
+        let track = [p.clone(), None, None, p.clone(), None, None, None, None, p];
+        assert_eq!(
+            run(&mut tracker, &track),
+            [
+                (0, GpsEvent::FixAcquired),
+                (7, GpsEvent::FixLost),
+                (8, GpsEvent::FixAcquired),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reacquired_fix_reports_distance_covered() {
+        let mut tracker = GpsTracker::default();
+        let mut track = vec![Some(at(0.0, 0.0)), None, None, None, None];
+        track.push(Some(at(2000.0, 0.0)));
+        let events = run(&mut tracker, &track);
+        assert_eq!(events[1], (4, GpsEvent::FixLost));
+        assert_eq!(events[2], (5, GpsEvent::FixAcquired));
+        assert!(matches!(events[3], (5, GpsEvent::Moved { distance_m }) if distance_m > 1990.0));
+    }
+
+    #[test]
+    fn test_custom_thresholds() {
+        let mut tracker = GpsTracker::new(GpsThresholds {
+            move_distance_m: 10.0,
+            start_speed_kmh: 2.0,
+            stop_speed_kmh: 1.0,
+            confirm: Duration::ZERO,
+        });
+        let now = Instant::now();
+        assert_eq!(
+            tracker.update(now, Some(&at(0.0, 0.0))),
+            [GpsEvent::FixAcquired]
+        );
+        assert_eq!(
+            tracker.update(now + POLL, Some(&at(15.0, 2.5))),
+            [
+                GpsEvent::Moved {
+                    distance_m: distance_m(&at(0.0, 0.0), &at(15.0, 0.0))
+                },
+                GpsEvent::StartedMoving
+            ]
+        );
+        assert_eq!(
+            tracker.update(now + POLL * 2, Some(&at(15.0, 0.5))),
+            [GpsEvent::Stopped]
+        );
+    }
+
+    #[test]
+    fn test_poll_rate_does_not_shorten_confirmation() {
+        // One sample is 200 ms, so each receiver update shows up five times.
+        let polls = |n: usize, p: Option<GpsPosition>| vec![p; n];
+        let parked = Some(at(0.0, 0.0));
+
+        // A one-second speed spike and a one-second dropout change nothing.
+        let mut track = polls(5, parked.clone());
+        track.extend(polls(5, Some(at(0.0, 30.0))));
+        track.extend(polls(5, parked.clone()));
+        track.extend(polls(5, None));
+        track.extend(polls(5, parked.clone()));
+        let mut tracker = GpsTracker::default();
+        assert_eq!(
+            run_every(&mut tracker, POLL, &track),
+            [(0, GpsEvent::FixAcquired)]
+        );
+
+        // Fifteen polls without a position span 2.8 s; the sixteenth,
+        // 3 s after the first miss, loses the fix.
+        let mut track = polls(1, parked.clone());
+        track.extend(polls(16, None));
+        let mut tracker = GpsTracker::default();
+        assert_eq!(
+            run_every(&mut tracker, POLL, &track),
+            [(0, GpsEvent::FixAcquired), (16, GpsEvent::FixLost)]
+        );
+    }
+
+    #[test]
+    fn test_event_display() {
+        assert_eq!(
+            GpsEvent::Moved { distance_m: 152.4 }.to_string(),
+            "moved 152 m"
+        );
+        assert_eq!(GpsEvent::StartedMoving.to_string(), "started moving");
+        assert_eq!(GpsEvent::FixLost.to_string(), "fix lost");
+    }
+
+    #[test]
+    fn test_gps_message_round_trip() {
+        for text in ["", "QRV 2m", "CQ CQ de N0CALL  ~!", "12345678901234567890"] {
+            let data = encode_gps_message(text).unwrap();
+            assert_eq!(data.len(), GPS_MESSAGE_LEN);
+            assert_eq!(decode_gps_message(&data).unwrap(), text.trim_end());
+        }
+    }
+
+    #[test]
+    fn test_gps_message_padding() {
+        assert_eq!(&encode_gps_message("hi").unwrap(), b"hi                  ");
+        assert_eq!(decode_gps_message(b"hi\0\0\0  \0").unwrap(), "hi");
+        assert_eq!(decode_gps_message(b"  hi there ").unwrap(), "  hi there");
+        assert_eq!(decode_gps_message(&[0x00; GPS_MESSAGE_LEN]).unwrap(), "");
+    }
+
+    #[test]
+    fn test_gps_message_rejects_bad_characters() {
+        let err = validate_gps_message("73 de caf\u{e9}").unwrap_err();
+        assert!(matches!(
+            err,
+            CivError::UnsupportedGpsMessageChar {
+                ch: '\u{e9}',
+                position: 10
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "unsupported character '\u{e9}' at position 10 in GPS message"
+        );
+        assert!(matches!(
+            validate_gps_message("tab\there"),
+            Err(CivError::UnsupportedGpsMessageChar {
+                ch: '\t',
+                position: 4
+            })
+        ));
+        assert!(decode_gps_message(b"bad\xFFbyte").is_err());
+    }
+
+    #[test]
+    fn test_gps_message_length() {
+        assert!(validate_gps_message(&"x".repeat(GPS_MESSAGE_LEN)).is_ok());
+        let err = validate_gps_message(&"x".repeat(GPS_MESSAGE_LEN + 1)).unwrap_err();
+        assert!(matches!(
+            err,
+            CivError::GpsMessageTooLong { len: 21, max: 20 }
+        ));
+        // A bad character is reported even when the text is also too long.
+        assert!(matches!(
+            validate_gps_message(&format!("{}\u{b0}", "x".repeat(30))),
+            Err(CivError::UnsupportedGpsMessageChar { position: 31, .. })
+        ));
+    }
+}
@@ -1,6 +1,9 @@
+pub mod band;
 pub mod bcd;
 pub mod command;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod frequency;
 pub mod gps;
 pub mod mode;
@@ -9,8 +12,9 @@ pub mod radio;
 pub mod response;
 pub mod transport;
 
-pub use error::{CivError, Result};
+pub use error::{CivError, FrameError, Result};
 pub use frequency::Frequency;
 pub use gps::GpsPosition;
 pub use mode::OperatingMode;
 pub use radio::{Radio, RadioConfig, RadioState, Vfo, VfoState};
+pub use response::ParseStrictness;
@@ -7,6 +7,7 @@ use crate::error::{CivError, Result};
 /// The ID-52A Plus supports FM, FM-N (narrow), AM, AM-N, and DV (D-STAR digital voice).
 /// CI-V encodes the mode as a (mode_byte, filter_byte) pair.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OperatingMode {
     /// FM (wide)
     Fm,
@@ -1,4 +1,4 @@
-use crate::error::{CivError, Result};
+use crate::error::{CivError, FrameError, Result};
 
 /// CI-V frame preamble byte.
 pub const PREAMBLE: u8 = 0xFE;
@@ -77,7 +77,7 @@ impl Frame {
         // Minimum frame: FE FE dst src cmd FD = 6 bytes
         let frame_bytes = &buf[start..=eom_pos];
         if frame_bytes.len() < 6 {
-            return Err(CivError::InvalidFrame);
+            return Err(CivError::InvalidFrame(FrameError::TooShort(frame_bytes.len())));
         }
 
         let dst = frame_bytes[2];
@@ -4,12 +4,12 @@ use std::time::{Duration, Instant};
 use log::{info, trace, warn};
 
 use crate::command::{Command, level_sub, meter_sub, tone_sub, various_sub};
-use crate::error::{CivError, Result};
+use crate::error::{CivError, FrameError, Result};
 use crate::frequency::Frequency;
 use crate::gps::{self, GpsPosition};
 use crate::mode::OperatingMode;
 use crate::protocol::{ADDR_CONTROLLER, ADDR_ID52, Frame};
-use crate::response::{self, Response};
+use crate::response::{self, ParseStrictness, Response};
 use crate::transport::Transport;
 
 // ---------------------------------------------------------------------------
@@ -18,6 +18,7 @@ use crate::transport::Transport;
 
 /// VFO selection.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Vfo {
     #[default]
     A,
@@ -45,6 +46,7 @@ impl fmt::Display for Vfo {
 
 /// Per-VFO state (frequency, mode, and tone/duplex settings).
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VfoState {
     pub frequency: Option<Frequency>,
     pub mode: Option<OperatingMode>,
@@ -69,6 +71,7 @@ pub struct VfoState {
 
 /// Snapshot of all radio state. `None` means not yet read or read failed.
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RadioState {
     pub vfo_a: VfoState,
     pub vfo_b: VfoState,
@@ -95,6 +98,8 @@ pub struct RadioConfig {
     pub baud_rate: u32,
     /// Timeout for waiting for a response.
     pub timeout: Duration,
+    /// How strictly response payload lengths are checked.
+    pub strictness: ParseStrictness,
 }
 
 impl Default for RadioConfig {
@@ -104,6 +109,7 @@ impl Default for RadioConfig {
             controller_addr: ADDR_CONTROLLER,
             baud_rate: 19200,
             timeout: Duration::from_millis(1000),
+            strictness: ParseStrictness::default(),
         }
     }
 }
@@ -177,7 +183,7 @@ impl Radio {
 
         // Read the actual response, skipping echo-back and unsolicited frames.
         let response_frame = self.read_response(command.command_byte())?;
-        response::parse_response(&response_frame, command)
+        response::parse_response_with(&response_frame, command, self.config.strictness)
     }
 
     /// Read a response frame from the radio (addressed to the controller).
@@ -256,7 +262,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadFrequency: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadFrequency",
+                )))
             }
         }
     }
@@ -268,7 +276,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetFrequency: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetFrequency",
+                )))
             }
         }
     }
@@ -280,7 +290,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadMode: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadMode",
+                )))
             }
         }
     }
@@ -292,7 +304,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetMode: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetMode",
+                )))
             }
         }
     }
@@ -304,7 +318,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadMeter(S): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadMeter(S)",
+                )))
             }
         }
     }
@@ -316,7 +332,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadLevel(AF): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadLevel(AF)",
+                )))
             }
         }
     }
@@ -328,7 +346,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetLevel(AF): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetLevel(AF)",
+                )))
             }
         }
     }
@@ -340,7 +360,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetLevel(SQL): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetLevel(SQL)",
+                )))
             }
         }
     }
@@ -352,7 +374,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadLevel(SQL): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadLevel(SQL)",
+                )))
             }
         }
     }
@@ -364,7 +388,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SelectVfoA: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SelectVfoA",
+                )))
             }
         }
     }
@@ -376,7 +402,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SelectVfoB: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SelectVfoB",
+                )))
             }
         }
     }
@@ -407,7 +435,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to PowerOn: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "PowerOn",
+                )))
             }
         }
     }
@@ -419,7 +449,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to PowerOff: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "PowerOff",
+                )))
             }
         }
     }
@@ -431,7 +463,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadLevel(RF_POWER): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadLevel(RF_POWER)",
+                )))
             }
         }
     }
@@ -443,7 +477,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetLevel(RF_POWER): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetLevel(RF_POWER)",
+                )))
             }
         }
     }
@@ -455,7 +491,23 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadVarious: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadVarious",
+                )))
+            }
+        }
+    }
+
+    /// Write a various function setting as a single raw byte.
+    pub fn set_various(&mut self, sub: u8, value: u8) -> Result<()> {
+        match self.send_command(&Command::SetVarious(sub, value))? {
+            Response::Ok => Ok(()),
+            Response::Ng => Err(CivError::Ng),
+            other => {
+                warn!("unexpected response to SetVarious: {:?}", other);
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetVarious",
+                )))
             }
         }
     }
@@ -472,7 +524,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadDuplex: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadDuplex",
+                )))
             }
         }
     }
@@ -484,7 +538,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadOffset: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadOffset",
+                )))
             }
         }
     }
@@ -496,7 +552,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadTone(Tx): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadTone(Tx)",
+                )))
             }
         }
     }
@@ -508,7 +566,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadTone(Rx): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadTone(Rx)",
+                )))
             }
         }
     }
@@ -520,7 +580,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadTone(DTCS): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadTone(DTCS)",
+                )))
             }
         }
     }
@@ -532,7 +594,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetDuplex: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetDuplex",
+                )))
             }
         }
     }
@@ -544,7 +608,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetOffset: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetOffset",
+                )))
             }
         }
     }
@@ -556,7 +622,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetVarious(ToneMode): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetVarious(ToneMode)",
+                )))
             }
         }
     }
@@ -568,7 +636,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetTone(Tx): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetTone(Tx)",
+                )))
             }
         }
     }
@@ -580,7 +650,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetTone(Rx): {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetTone(Rx)",
+                )))
             }
         }
     }
@@ -592,7 +664,9 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to SetDtcs: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetDtcs",
+                )))
             }
         }
     }
@@ -604,7 +678,97 @@ impl Radio {
             Response::Ng => Err(CivError::Ng),
             other => {
                 warn!("unexpected response to ReadGpsPosition: {:?}", other);
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadGpsPosition",
+                )))
+            }
+        }
+    }
+
+    /// Read the GPS message, without its trailing padding.
+    pub fn read_gps_message(&mut self) -> Result<String> {
+        match self.send_command(&Command::ReadGpsMessage)? {
+            Response::GpsMessage(text) => Ok(text),
+            Response::Ng => Err(CivError::Ng),
+            other => {
+                warn!("unexpected response to ReadGpsMessage: {:?}", other);
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadGpsMessage",
+                )))
+            }
+        }
+    }
+
+    /// Set the GPS message (up to 20 printable ASCII characters).
+    ///
+    /// The text is validated before anything is sent to the radio.
+    pub fn set_gps_message(&mut self, text: &str) -> Result<()> {
+        gps::validate_gps_message(text)?;
+        match self.send_command(&Command::SetGpsMessage(text.to_string()))? {
+            Response::Ok => Ok(()),
+            Response::Ng => Err(CivError::Ng),
+            other => {
+                warn!("unexpected response to SetGpsMessage: {:?}", other);
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetGpsMessage",
+                )))
+            }
+        }
+    }
+
+    /// Read a various function setting. Returns every raw value byte.
+    pub fn read_various_raw(&mut self, sub: u8) -> Result<Vec<u8>> {
+        match self.send_command(&Command::ReadVariousRaw(sub))? {
+            Response::VariousRaw(_, value) => Ok(value),
+            Response::Ng => Err(CivError::Ng),
+            other => {
+                warn!("unexpected response to ReadVariousRaw: {:?}", other);
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadVariousRaw",
+                )))
+            }
+        }
+    }
+
+    /// Write a various function setting. The value bytes are sent as is.
+    pub fn set_various_raw(&mut self, sub: u8, value: &[u8]) -> Result<()> {
+        match self.send_command(&Command::SetVariousRaw(sub, value.to_vec()))? {
+            Response::Ok => Ok(()),
+            Response::Ng => Err(CivError::Ng),
+            other => {
+                warn!("unexpected response to SetVariousRaw: {:?}", other);
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetVariousRaw",
+                )))
+            }
+        }
+    }
+
+    /// Read a menu setting (0x1A 0x05). Returns the raw value bytes.
+    pub fn read_setting(&mut self, item: u16) -> Result<Vec<u8>> {
+        match self.send_command(&Command::ReadSetting(item))? {
+            Response::Setting(_, value) => Ok(value),
+            Response::Ng => Err(CivError::Ng),
+            other => {
+                warn!("unexpected response to ReadSetting: {:?}", other);
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "ReadSetting",
+                )))
+            }
+        }
+    }
+
+    /// Write a menu setting as raw value bytes. Nothing checks that the
+    /// value suits the item.
+    pub fn set_setting(&mut self, item: u16, value: &[u8]) -> Result<()> {
+        match self.send_command(&Command::SetSetting(item, value.to_vec()))? {
+            Response::Ok => Ok(()),
+            Response::Ng => Err(CivError::Ng),
+            other => {
+                warn!("unexpected response to SetSetting: {:?}", other);
+                Err(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                    "SetSetting",
+                )))
             }
         }
     }
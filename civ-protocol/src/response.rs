@@ -1,7 +1,12 @@
+use std::cmp::Ordering;
+
+use log::debug;
+
 use crate::bcd;
-use crate::command::{Command, cmd};
-use crate::error::{CivError, Result};
+use crate::command::{Command, cmd, gps_sub, setting_sub};
+use crate::error::{CivError, FrameError, Result};
 use crate::frequency::Frequency;
+use crate::gps;
 use crate::mode::OperatingMode;
 use crate::protocol::Frame;
 
@@ -49,6 +54,17 @@ pub struct RawGpsPosition {
     pub utc_second: u8,
 }
 
+/// How strictly payload lengths are checked when parsing responses.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ParseStrictness {
+    /// The payload must be exactly the expected length.
+    Strict,
+    /// Extra trailing bytes are ignored (some firmware pads responses);
+    /// short payloads are still rejected.
+    #[default]
+    Lenient,
+}
+
 /// A typed response from the radio.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Response {
@@ -69,6 +85,9 @@ pub enum Response {
     /// Various function setting (response to ReadVarious). Contains (sub_command, raw_value).
     /// The value is a single raw byte, NOT BCD-decoded.
     Various(u8, u8),
+    /// Various function setting (response to ReadVariousRaw). Contains
+    /// (sub_command, raw value bytes), all of the payload after the sub-command.
+    VariousRaw(u8, Vec<u8>),
     /// Duplex direction (response to ReadDuplex).
     /// 0x10=Simplex, 0x11=DUP-, 0x12=DUP+. The sub_command IS the data.
     Duplex(u8),
@@ -82,11 +101,27 @@ pub enum Response {
     DtcsCode(u8, u8, u16),
     /// GPS position data (response to ReadGpsPosition).
     GpsPosition(RawGpsPosition),
+    /// GPS message text with the padding removed (response to ReadGpsMessage).
+    GpsMessage(String),
+    /// Menu setting (response to ReadSetting). Contains (item, raw value bytes).
+    /// The value is returned undecoded; its layout depends on the item.
+    Setting(u16, Vec<u8>),
 }
 
 /// Parse a response `Frame` into a typed `Response`, using the original `Command`
 /// to disambiguate commands that share the same command byte.
+///
+/// Uses the default [`ParseStrictness`]; see [`parse_response_with`].
 pub fn parse_response(frame: &Frame, command: &Command) -> Result<Response> {
+    parse_response_with(frame, command, ParseStrictness::default())
+}
+
+/// Like [`parse_response`], with an explicit payload length policy.
+pub fn parse_response_with(
+    frame: &Frame,
+    command: &Command,
+    strictness: ParseStrictness,
+) -> Result<Response> {
     // Handle OK/NG first — these apply to any command.
     if frame.is_ok() {
         return Ok(Response::Ok);
@@ -103,27 +138,74 @@ pub fn parse_response(frame: &Frame, command: &Command) -> Result<Response> {
             if frame.command == cmd::SET_FREQ || frame.command == cmd::READ_FREQ {
                 parse_frequency_response(frame)
             } else {
-                Err(CivError::InvalidFrame)
+                Err(CivError::InvalidFrame(FrameError::UnexpectedCommand(
+                    frame.command,
+                )))
             }
         }
         Command::ReadMode => parse_mode_response(frame),
         Command::SetMode(_) => Ok(Response::Ok),
         Command::SelectVfoA | Command::SelectVfoB => Ok(Response::Ok),
-        Command::ReadLevel(sub) => parse_level_response(frame, *sub),
+        Command::ReadLevel(sub) => parse_level_response(frame, *sub, strictness),
         Command::SetLevel(_, _) => Ok(Response::Ok),
-        Command::ReadMeter(sub) => parse_meter_response(frame, *sub),
+        Command::ReadMeter(sub) => parse_meter_response(frame, *sub, strictness),
         Command::PowerOn | Command::PowerOff => Ok(Response::Ok),
         Command::ReadTransceiverId => parse_transceiver_id_response(frame),
         Command::ReadVarious(sub) => parse_various_response(frame, *sub),
+        Command::ReadVariousRaw(sub) => parse_various_raw_response(frame, *sub),
         Command::ReadDuplex => parse_duplex_response(frame),
-        Command::ReadOffset => parse_offset_response(frame),
-        Command::ReadTone(sub) => parse_tone_response(frame, *sub),
+        Command::ReadOffset => parse_offset_response(frame, strictness),
+        Command::ReadTone(sub) => parse_tone_response(frame, *sub, strictness),
         Command::SetDuplex(_) => Ok(Response::Ok),
         Command::SetOffset(_) => Ok(Response::Ok),
-        Command::SetVarious(_, _) => Ok(Response::Ok),
+        Command::SetVarious(_, _) | Command::SetVariousRaw(_, _) => Ok(Response::Ok),
         Command::SetTone(_, _) => Ok(Response::Ok),
         Command::SetDtcs(_, _, _) => Ok(Response::Ok),
-        Command::ReadGpsPosition => parse_gps_position_response(frame),
+        Command::ReadGpsPosition => parse_gps_position_response(frame, strictness),
+        Command::ReadGpsMessage => parse_gps_message_response(frame, strictness),
+        Command::SetGpsMessage(_) => Ok(Response::Ok),
+        Command::ReadSetting(item) => parse_setting_response(frame, *item),
+        Command::SetSetting(_, _) => Ok(Response::Ok),
+    }
+}
+
+/// Check `payload` against the `expected` length for a `what` response.
+///
+/// Shorter payloads are always rejected. Longer ones are rejected in strict
+/// mode and truncated in lenient mode, with a debug note so the quirk stays
+/// visible in logs.
+fn expect_len<'a>(
+    payload: &'a [u8],
+    expected: usize,
+    strictness: ParseStrictness,
+    what: &str,
+) -> Result<&'a [u8]> {
+    match payload.len().cmp(&expected) {
+        Ordering::Equal => Ok(payload),
+        Ordering::Greater if strictness == ParseStrictness::Lenient => {
+            debug!(
+                "{what} response: ignoring {} trailing byte(s) {:02X?}",
+                payload.len() - expected,
+                &payload[expected..]
+            );
+            Ok(&payload[..expected])
+        }
+        _ => Err(CivError::InvalidFrame(FrameError::Length {
+            expected,
+            actual: payload.len(),
+        })),
+    }
+}
+
+/// Check that `frame` carries the `expected` sub-command and return it.
+fn expect_sub(frame: &Frame, expected: u8) -> Result<u8> {
+    match frame.sub_command {
+        Some(sub) if sub == expected => Ok(sub),
+        Some(actual) => Err(CivError::InvalidFrame(FrameError::SubCommand {
+            expected,
+            actual,
+        })),
+        None => Err(CivError::InvalidFrame(FrameError::MissingSubCommand)),
     }
 }
 
@@ -140,7 +222,10 @@ fn parse_frequency_response(frame: &Frame) -> Result<Response> {
     freq_bytes.extend_from_slice(&frame.data);
 
     if freq_bytes.len() != 5 {
-        return Err(CivError::InvalidFrame);
+        return Err(CivError::InvalidFrame(FrameError::Length {
+            expected: 5,
+            actual: freq_bytes.len(),
+        }));
     }
 
     let mut arr = [0u8; 5];
@@ -154,41 +239,48 @@ fn parse_frequency_response(frame: &Frame) -> Result<Response> {
 /// Mode response payload: `<mode_byte> <filter_byte>`
 fn parse_mode_response(frame: &Frame) -> Result<Response> {
     // The mode response has the mode byte as sub_command and filter as data[0].
-    let mode_byte = frame.sub_command.ok_or(CivError::InvalidFrame)?;
-    let filter_byte = frame.data.first().copied().ok_or(CivError::InvalidFrame)?;
+    let mode_byte = frame
+        .sub_command
+        .ok_or(CivError::InvalidFrame(FrameError::MissingSubCommand))?;
+    let Some(&filter_byte) = frame.data.first() else {
+        return Err(CivError::InvalidFrame(FrameError::ShortData {
+            min: 1,
+            actual: 0,
+        }));
+    };
     let mode = OperatingMode::from_civ_bytes(mode_byte, filter_byte)?;
     Ok(Response::Mode(mode))
 }
 
 /// Parse a level response frame.
-fn parse_level_response(frame: &Frame, expected_sub: u8) -> Result<Response> {
-    let sub = frame.sub_command.ok_or(CivError::InvalidFrame)?;
-    if sub != expected_sub {
-        return Err(CivError::InvalidFrame);
-    }
-    if frame.data.len() != 2 {
-        return Err(CivError::InvalidFrame);
-    }
-    let value = bcd::decode_bcd_be(&frame.data)? as u16;
+fn parse_level_response(
+    frame: &Frame,
+    expected_sub: u8,
+    strictness: ParseStrictness,
+) -> Result<Response> {
+    let sub = expect_sub(frame, expected_sub)?;
+    let data = expect_len(&frame.data, 2, strictness, "level")?;
+    let value = bcd::decode_bcd_be(data)? as u16;
     Ok(Response::Level(sub, value))
 }
 
 /// Parse a meter response frame.
-fn parse_meter_response(frame: &Frame, expected_sub: u8) -> Result<Response> {
-    let sub = frame.sub_command.ok_or(CivError::InvalidFrame)?;
-    if sub != expected_sub {
-        return Err(CivError::InvalidFrame);
-    }
-    if frame.data.len() != 2 {
-        return Err(CivError::InvalidFrame);
-    }
-    let value = bcd::decode_bcd_be(&frame.data)? as u16;
+fn parse_meter_response(
+    frame: &Frame,
+    expected_sub: u8,
+    strictness: ParseStrictness,
+) -> Result<Response> {
+    let sub = expect_sub(frame, expected_sub)?;
+    let data = expect_len(&frame.data, 2, strictness, "meter")?;
+    let value = bcd::decode_bcd_be(data)? as u16;
     Ok(Response::Meter(sub, value))
 }
 
 /// Parse a transceiver ID response frame.
 fn parse_transceiver_id_response(frame: &Frame) -> Result<Response> {
-    let id = frame.sub_command.ok_or(CivError::InvalidFrame)?;
+    let id = frame
+        .sub_command
+        .ok_or(CivError::InvalidFrame(FrameError::MissingSubCommand))?;
     Ok(Response::TransceiverId(id))
 }
 
@@ -197,19 +289,37 @@ fn parse_transceiver_id_response(frame: &Frame) -> Result<Response> {
 /// The response is a single raw byte (NOT BCD-decoded).
 /// Frame format: `[cmd=0x16] [sub=0x5D] [data: 1 byte raw value]`
 fn parse_various_response(frame: &Frame, expected_sub: u8) -> Result<Response> {
-    let sub = frame.sub_command.ok_or(CivError::InvalidFrame)?;
-    if sub != expected_sub {
-        return Err(CivError::InvalidFrame);
-    }
-    let value = frame.data.first().copied().ok_or(CivError::InvalidFrame)?;
+    let sub = expect_sub(frame, expected_sub)?;
+    let Some(&value) = frame.data.first() else {
+        return Err(CivError::InvalidFrame(FrameError::ShortData {
+            min: 1,
+            actual: 0,
+        }));
+    };
     Ok(Response::Various(sub, value))
 }
 
+/// Parse a various function response frame, keeping every value byte.
+///
+/// Frame format: `[cmd=0x16] [sub] [data: 1+ raw bytes]`
+fn parse_various_raw_response(frame: &Frame, expected_sub: u8) -> Result<Response> {
+    let sub = expect_sub(frame, expected_sub)?;
+    if frame.data.is_empty() {
+        return Err(CivError::InvalidFrame(FrameError::ShortData {
+            min: 1,
+            actual: 0,
+        }));
+    }
+    Ok(Response::VariousRaw(sub, frame.data.clone()))
+}
+
 /// Parse a duplex direction response frame.
 ///
 /// The sub_command byte IS the data: 0x10=Simplex, 0x11=DUP-, 0x12=DUP+.
 fn parse_duplex_response(frame: &Frame) -> Result<Response> {
-    let duplex = frame.sub_command.ok_or(CivError::InvalidFrame)?;
+    let duplex = frame
+        .sub_command
+        .ok_or(CivError::InvalidFrame(FrameError::MissingSubCommand))?;
     Ok(Response::Duplex(duplex))
 }
 
@@ -221,19 +331,17 @@ fn parse_duplex_response(frame: &Frame) -> Result<Response> {
 ///   byte 2: (10 MHz)(1 MHz)
 ///
 /// Decoded via standard LE BCD, then multiplied by 100 to get Hz.
-fn parse_offset_response(frame: &Frame) -> Result<Response> {
+fn parse_offset_response(frame: &Frame, strictness: ParseStrictness) -> Result<Response> {
     let mut offset_bytes = Vec::with_capacity(3);
     if let Some(sc) = frame.sub_command {
         offset_bytes.push(sc);
     }
     offset_bytes.extend_from_slice(&frame.data);
 
-    if offset_bytes.len() != 3 {
-        return Err(CivError::InvalidFrame);
-    }
+    let offset_bytes = expect_len(&offset_bytes, 3, strictness, "offset")?;
 
     // LE BCD decode gives units of 100 Hz (the smallest digit pair).
-    let raw = bcd::decode_bcd_le(&offset_bytes)?;
+    let raw = bcd::decode_bcd_le(offset_bytes)?;
     let hz = raw * 100;
     let freq = Frequency::from_hz(hz)?;
     Ok(Response::Offset(freq))
@@ -250,20 +358,19 @@ fn parse_offset_response(frame: &Frame) -> Result<Response> {
 ///   High nibble of byte 0 = Tx polarity (0=Normal, 1=Reverse)
 ///   Low nibble of byte 0 = Rx polarity
 ///   Example: code 023, normal → `[0x00, 0x00, 0x23]`
-fn parse_tone_response(frame: &Frame, expected_sub: u8) -> Result<Response> {
-    let sub = frame.sub_command.ok_or(CivError::InvalidFrame)?;
-    if sub != expected_sub {
-        return Err(CivError::InvalidFrame);
-    }
-    if frame.data.len() != 3 {
-        return Err(CivError::InvalidFrame);
-    }
+fn parse_tone_response(
+    frame: &Frame,
+    expected_sub: u8,
+    strictness: ParseStrictness,
+) -> Result<Response> {
+    let sub = expect_sub(frame, expected_sub)?;
+    let data = expect_len(&frame.data, 3, strictness, "tone")?;
 
     match sub {
         0x00 | 0x01 => {
             // Tone frequency: [0x00, hundreds_tens, units_tenths]
-            let hundreds_tens = frame.data[1];
-            let units_tenths = frame.data[2];
+            let hundreds_tens = data[1];
+            let units_tenths = data[2];
             let ht = bcd::decode_bcd_be(&[hundreds_tens])? as u16;
             let ut = bcd::decode_bcd_be(&[units_tenths])? as u16;
             let freq_tenths = ht * 100 + ut;
@@ -271,15 +378,15 @@ fn parse_tone_response(frame: &Frame, expected_sub: u8) -> Result<Response> {
         }
         0x02 => {
             // DTCS code: [polarity, first_digit, second_third]
-            let polarity_byte = frame.data[0];
+            let polarity_byte = data[0];
             let tx_pol = (polarity_byte >> 4) & 0x0F;
             let rx_pol = polarity_byte & 0x0F;
-            let first = bcd::decode_bcd_be(&[frame.data[1]])? as u16;
-            let second_third = bcd::decode_bcd_be(&[frame.data[2]])? as u16;
+            let first = bcd::decode_bcd_be(&[data[1]])? as u16;
+            let second_third = bcd::decode_bcd_be(&[data[2]])? as u16;
             let code = first * 100 + second_third;
             Ok(Response::DtcsCode(tx_pol, rx_pol, code))
         }
-        _ => Err(CivError::InvalidFrame),
+        other => Err(CivError::InvalidFrame(FrameError::UnknownSubCommand(other))),
     }
 }
 
@@ -299,17 +406,11 @@ fn lo(b: u8) -> u8 {
 /// Each byte holds two BCD digits (high nibble = H, low nibble = L).
 ///
 /// See the user-provided byte layout documentation for full details.
-fn parse_gps_position_response(frame: &Frame) -> Result<Response> {
-    let sub = frame.sub_command.ok_or(CivError::InvalidFrame)?;
-    if sub != 0x00 {
-        return Err(CivError::InvalidFrame);
-    }
+fn parse_gps_position_response(frame: &Frame, strictness: ParseStrictness) -> Result<Response> {
+    expect_sub(frame, gps_sub::MY_POSITION)?;
     // We expect 27 bytes of data (bytes 1–27 in the spec).
     // The sub_command byte is already consumed, so all 27 should be in frame.data.
-    if frame.data.len() != 27 {
-        return Err(CivError::InvalidFrame);
-    }
-    let d = &frame.data;
+    let d = expect_len(&frame.data, 27, strictness, "GPS position")?;
 
     // Bytes 1-5: Latitude (dd°mm.mmm)
     let lat_deg = hi(d[0]) * 10 + lo(d[0]);
@@ -378,6 +479,36 @@ fn parse_gps_position_response(frame: &Frame) -> Result<Response> {
     }))
 }
 
+/// Parse a GPS message response frame (command 0x23, sub 0x02).
+///
+/// The response data is the 20-character message, padded with spaces.
+fn parse_gps_message_response(frame: &Frame, strictness: ParseStrictness) -> Result<Response> {
+    expect_sub(frame, gps_sub::MESSAGE)?;
+    let d = expect_len(&frame.data, gps::GPS_MESSAGE_LEN, strictness, "GPS message")?;
+    Ok(Response::GpsMessage(gps::decode_gps_message(d)?))
+}
+
+/// Parse a menu setting response frame.
+///
+/// Frame format: `[cmd=0x1A] [sub=0x05] [item: 2 bytes BCD] [value: 1+ raw bytes]`
+fn parse_setting_response(frame: &Frame, expected_item: u16) -> Result<Response> {
+    expect_sub(frame, setting_sub::MENU)?;
+    if frame.data.len() < 3 {
+        return Err(CivError::InvalidFrame(FrameError::ShortData {
+            min: 3,
+            actual: frame.data.len(),
+        }));
+    }
+    let item = bcd::decode_bcd_be(&frame.data[..2])? as u16;
+    if item != expected_item {
+        return Err(CivError::InvalidFrame(FrameError::SettingItem {
+            expected: expected_item,
+            actual: item,
+        }));
+    }
+    Ok(Response::Setting(item, frame.data[2..].to_vec()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,6 +613,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_various_raw_keeps_whole_payload() {
+        let frame = make_response_frame(cmd::VARIOUS, Some(0x02), vec![0x01, 0x23]);
+        let resp = parse_response(&frame, &Command::ReadVariousRaw(0x02)).unwrap();
+        assert_eq!(resp, Response::VariousRaw(0x02, vec![0x01, 0x23]));
+
+        // Wrong sub-command, missing value.
+        assert!(parse_response(&frame, &Command::ReadVariousRaw(0x03)).is_err());
+        let empty = make_response_frame(cmd::VARIOUS, Some(0x02), vec![]);
+        assert!(parse_response(&empty, &Command::ReadVariousRaw(0x02)).is_err());
+    }
+
     #[test]
     fn test_parse_duplex_simplex() {
         let frame = make_response_frame(cmd::READ_DUPLEX, Some(0x10), vec![]);
@@ -637,4 +780,177 @@ mod tests {
             })
         );
     }
+
+    /// GPS payload from `test_parse_gps_position`.
+    const GPS_PAYLOAD: [u8; 27] = [
+        0x40, 0x41, 0x89, 0x20, 0x01, 0x00, 0x74, 0x02, 0x53, 0x60, 0x00, 0x00, 0x01, 0x02, 0x00,
+        0x12, 0x50, 0x00, 0x00, 0x52, 0x20, 0x26, 0x02, 0x17, 0x15, 0x30, 0x45,
+    ];
+
+    /// Parse `command`'s response with an exact, a one-byte-short and a
+    /// one-byte-padded payload under both strictness modes.
+    fn check_lengths(command: Command, cmd_byte: u8, sub: u8, exact: &[u8], expected: Response) {
+        let frame = |data: &[u8]| make_response_frame(cmd_byte, Some(sub), data.to_vec());
+        let short = &exact[..exact.len() - 1];
+        let padded = [exact, &[0x00]].concat();
+
+        for strictness in [ParseStrictness::Strict, ParseStrictness::Lenient] {
+            let parse = |data: &[u8]| parse_response_with(&frame(data), &command, strictness);
+            assert_eq!(parse(exact).unwrap(), expected, "exact, {strictness:?}");
+            assert!(
+                matches!(
+                    parse(short),
+                    Err(CivError::InvalidFrame(FrameError::Length { .. }))
+                ),
+                "short, {strictness:?}"
+            );
+            match strictness {
+                ParseStrictness::Strict => assert!(
+                    matches!(
+                        parse(&padded),
+                        Err(CivError::InvalidFrame(FrameError::Length { .. }))
+                    ),
+                    "padded, strict"
+                ),
+                ParseStrictness::Lenient => {
+                    assert_eq!(parse(&padded).unwrap(), expected, "padded, lenient")
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strictness_level() {
+        check_lengths(
+            Command::ReadLevel(level_sub::AF_LEVEL),
+            cmd::LEVEL,
+            level_sub::AF_LEVEL,
+            &[0x01, 0x28],
+            Response::Level(level_sub::AF_LEVEL, 128),
+        );
+    }
+
+    #[test]
+    fn test_strictness_meter() {
+        check_lengths(
+            Command::ReadMeter(meter_sub::S_METER),
+            cmd::METER,
+            meter_sub::S_METER,
+            &[0x00, 0x50],
+            Response::Meter(meter_sub::S_METER, 50),
+        );
+    }
+
+    #[test]
+    fn test_strictness_tone() {
+        use crate::command::tone_sub;
+        check_lengths(
+            Command::ReadTone(tone_sub::REPEATER_TONE),
+            cmd::TONE,
+            tone_sub::REPEATER_TONE,
+            &[0x00, 0x14, 0x13],
+            Response::ToneFrequency(tone_sub::REPEATER_TONE, 1413),
+        );
+        check_lengths(
+            Command::ReadTone(tone_sub::DTCS),
+            cmd::TONE,
+            tone_sub::DTCS,
+            &[0x10, 0x07, 0x54],
+            Response::DtcsCode(1, 0, 754),
+        );
+    }
+
+    #[test]
+    fn test_strictness_offset() {
+        // The first offset byte travels as the sub-command.
+        check_lengths(
+            Command::ReadOffset,
+            cmd::READ_OFFSET,
+            0x00,
+            &[0x00, 0x05],
+            Response::Offset(Frequency::from_hz(5_000_000).unwrap()),
+        );
+    }
+
+    #[test]
+    fn test_strictness_gps_position() {
+        let expected = parse_response(
+            &make_response_frame(cmd::READ_GPS, Some(0x00), GPS_PAYLOAD.to_vec()),
+            &Command::ReadGpsPosition,
+        )
+        .unwrap();
+        check_lengths(
+            Command::ReadGpsPosition,
+            cmd::READ_GPS,
+            0x00,
+            &GPS_PAYLOAD,
+            expected,
+        );
+    }
+
+    #[test]
+    fn test_strictness_gps_message() {
+        check_lengths(
+            Command::ReadGpsMessage,
+            cmd::READ_GPS,
+            gps_sub::MESSAGE,
+            b"QRV 2m              ",
+            Response::GpsMessage("QRV 2m".into()),
+        );
+    }
+
+    #[test]
+    fn test_gps_message_round_trip() {
+        let sent = Command::SetGpsMessage("73 de N0CALL".into())
+            .to_frame()
+            .unwrap();
+        let reply = make_response_frame(cmd::READ_GPS, sent.sub_command, sent.data);
+        let resp = parse_response(&reply, &Command::ReadGpsMessage).unwrap();
+        assert_eq!(resp, Response::GpsMessage("73 de N0CALL".into()));
+
+        // A position reply is not a message reply, and vice versa.
+        let position = make_response_frame(cmd::READ_GPS, Some(0x00), GPS_PAYLOAD.to_vec());
+        assert!(parse_response(&position, &Command::ReadGpsMessage).is_err());
+        assert!(parse_response(&reply, &Command::ReadGpsPosition).is_err());
+    }
+
+    #[test]
+    fn test_parse_setting() {
+        let frame = make_response_frame(cmd::SETTING, Some(0x05), vec![0x01, 0x23, 0x00, 0x42]);
+        let resp = parse_response(&frame, &Command::ReadSetting(123)).unwrap();
+        assert_eq!(resp, Response::Setting(123, vec![0x00, 0x42]));
+
+        // Wrong item, missing value, wrong sub-command.
+        assert!(parse_response(&frame, &Command::ReadSetting(124)).is_err());
+        let empty = make_response_frame(cmd::SETTING, Some(0x05), vec![0x01, 0x23]);
+        assert!(parse_response(&empty, &Command::ReadSetting(123)).is_err());
+        let other = make_response_frame(cmd::SETTING, Some(0x06), vec![0x01, 0x23, 0x00]);
+        assert!(parse_response(&other, &Command::ReadSetting(123)).is_err());
+    }
+
+    #[test]
+    fn test_default_strictness_is_lenient() {
+        assert_eq!(ParseStrictness::default(), ParseStrictness::Lenient);
+        let frame = make_response_frame(
+            cmd::LEVEL,
+            Some(level_sub::AF_LEVEL),
+            vec![0x01, 0x28, 0xFF],
+        );
+        let resp = parse_response(&frame, &Command::ReadLevel(level_sub::AF_LEVEL)).unwrap();
+        assert_eq!(resp, Response::Level(level_sub::AF_LEVEL, 128));
+    }
+
+    #[test]
+    fn test_expect_len() {
+        let data = [1, 2, 3, 4];
+        for strictness in [ParseStrictness::Strict, ParseStrictness::Lenient] {
+            assert_eq!(expect_len(&data, 4, strictness, "t").unwrap(), &data);
+            assert!(expect_len(&data, 5, strictness, "t").is_err());
+        }
+        assert!(expect_len(&data, 3, ParseStrictness::Strict, "t").is_err());
+        assert_eq!(
+            expect_len(&data, 3, ParseStrictness::Lenient, "t").unwrap(),
+            &[1, 2, 3]
+        );
+    }
 }
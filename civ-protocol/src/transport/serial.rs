@@ -46,9 +46,7 @@ impl Transport for SerialTransport {
     }
 
     fn set_read_timeout(&mut self, timeout: Duration) -> io::Result<()> {
-        self.port
-            .set_timeout(timeout)
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+        self.port.set_timeout(timeout).map_err(io::Error::other)
     }
 }
 
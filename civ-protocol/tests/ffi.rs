@@ -0,0 +1,192 @@
+//! Exercises the `ffi` feature through the C ABI: the functions are declared
+//! here as `extern "C"` and resolved by the linker, exactly as a C caller would.
+
+#![cfg(feature = "ffi")]
+
+use std::mem::{align_of, offset_of, size_of};
+use std::path::PathBuf;
+use std::process::Command;
+use std::ptr;
+
+use civ_protocol::ffi::{CivFrame, CivStatus};
+
+unsafe extern "C" {
+    fn civ_encode_set_frequency(hz: u64, out_buf: *mut u8, out_len: *mut usize) -> CivStatus;
+    fn civ_parse_frame(bytes: *const u8, len: usize, out: *mut CivFrame) -> CivStatus;
+    fn civ_frame_free(frame: *mut CivFrame);
+}
+
+fn empty_frame() -> CivFrame {
+    CivFrame {
+        dst: 0xAA,
+        src: 0xAA,
+        command: 0xAA,
+        has_sub_command: 0xAA,
+        sub_command: 0xAA,
+        data: ptr::null_mut(),
+        data_len: 0,
+        consumed: 0,
+    }
+}
+
+#[test]
+fn test_frame_layout_matches_c() {
+    let word = size_of::<usize>();
+    assert_eq!(offset_of!(CivFrame, dst), 0);
+    assert_eq!(offset_of!(CivFrame, src), 1);
+    assert_eq!(offset_of!(CivFrame, command), 2);
+    assert_eq!(offset_of!(CivFrame, has_sub_command), 3);
+    assert_eq!(offset_of!(CivFrame, sub_command), 4);
+    assert_eq!(offset_of!(CivFrame, data), word);
+    assert_eq!(offset_of!(CivFrame, data_len), 2 * word);
+    assert_eq!(offset_of!(CivFrame, consumed), 3 * word);
+    assert_eq!(size_of::<CivFrame>(), 4 * word);
+    assert_eq!(align_of::<CivFrame>(), word);
+    assert_eq!(size_of::<CivStatus>(), 4);
+}
+
+#[test]
+fn test_status_codes() {
+    assert_eq!(CivStatus::Ok as i32, 0);
+    assert_eq!(CivStatus::NullPointer as i32, -1);
+    assert_eq!(CivStatus::BufferTooSmall as i32, -2);
+    assert_eq!(CivStatus::InvalidFrame as i32, -3);
+    assert_eq!(CivStatus::Incomplete as i32, -4);
+    assert_eq!(CivStatus::FrequencyOutOfRange as i32, -5);
+    assert_eq!(CivStatus::Panic as i32, -99);
+}
+
+#[test]
+fn test_encode_set_frequency() {
+    let mut buf = [0u8; 16];
+    let mut len = buf.len();
+    let status = unsafe { civ_encode_set_frequency(145_000_000, buf.as_mut_ptr(), &mut len) };
+    assert_eq!(status, CivStatus::Ok);
+    assert_eq!(
+        &buf[..len],
+        &[
+            0xFE, 0xFE, 0xB4, 0xE0, 0x05, 0x00, 0x00, 0x00, 0x45, 0x01, 0xFD
+        ]
+    );
+}
+
+#[test]
+fn test_encode_buffer_too_small_reports_required_size() {
+    let mut buf = [0u8; 4];
+    let mut len = buf.len();
+    let status = unsafe { civ_encode_set_frequency(145_000_000, buf.as_mut_ptr(), &mut len) };
+    assert_eq!(status, CivStatus::BufferTooSmall);
+    assert_eq!(len, 11);
+    assert_eq!(buf, [0u8; 4], "nothing is written on failure");
+}
+
+#[test]
+fn test_encode_errors() {
+    let mut buf = [0u8; 16];
+    let mut len = buf.len();
+    let status = unsafe { civ_encode_set_frequency(10_000_000_000, buf.as_mut_ptr(), &mut len) };
+    assert_eq!(status, CivStatus::FrequencyOutOfRange);
+
+    let status = unsafe { civ_encode_set_frequency(145_000_000, ptr::null_mut(), &mut len) };
+    assert_eq!(status, CivStatus::NullPointer);
+    let status =
+        unsafe { civ_encode_set_frequency(145_000_000, buf.as_mut_ptr(), ptr::null_mut()) };
+    assert_eq!(status, CivStatus::NullPointer);
+}
+
+#[test]
+fn test_parse_frame_and_free() {
+    // Garbage, then a level response: 14 01 01 28.
+    let bytes = [
+        0x00, 0xFE, 0xFE, 0xE0, 0xB4, 0x14, 0x01, 0x01, 0x28, 0xFD, 0xFE,
+    ];
+    let mut frame = empty_frame();
+    let status = unsafe { civ_parse_frame(bytes.as_ptr(), bytes.len(), &mut frame) };
+    assert_eq!(status, CivStatus::Ok);
+    assert_eq!((frame.dst, frame.src, frame.command), (0xE0, 0xB4, 0x14));
+    assert_eq!((frame.has_sub_command, frame.sub_command), (1, 0x01));
+    assert_eq!(frame.consumed, 10);
+    let data = unsafe { std::slice::from_raw_parts(frame.data, frame.data_len) };
+    assert_eq!(data, &[0x01, 0x28]);
+
+    unsafe { civ_frame_free(&mut frame) };
+    assert!(frame.data.is_null());
+    assert_eq!(frame.data_len, 0);
+    // A second free and a null free are both no-ops.
+    unsafe { civ_frame_free(&mut frame) };
+    unsafe { civ_frame_free(ptr::null_mut()) };
+}
+
+#[test]
+fn test_parse_frame_without_payload_owns_nothing() {
+    let bytes = [0xFE, 0xFE, 0xE0, 0xB4, 0xFB, 0xFD];
+    let mut frame = empty_frame();
+    let status = unsafe { civ_parse_frame(bytes.as_ptr(), bytes.len(), &mut frame) };
+    assert_eq!(status, CivStatus::Ok);
+    assert_eq!(frame.has_sub_command, 0);
+    assert!(frame.data.is_null());
+    unsafe { civ_frame_free(&mut frame) };
+}
+
+#[test]
+fn test_parse_frame_errors_leave_frame_zeroed() {
+    let mut frame = empty_frame();
+
+    let incomplete = [0xFE, 0xFE, 0xE0, 0xB4, 0x03];
+    let status = unsafe { civ_parse_frame(incomplete.as_ptr(), incomplete.len(), &mut frame) };
+    assert_eq!(status, CivStatus::Incomplete);
+    assert_eq!((frame.dst, frame.command, frame.data_len), (0, 0, 0));
+
+    let too_short = [0xFE, 0xFE, 0xFD];
+    let status = unsafe { civ_parse_frame(too_short.as_ptr(), too_short.len(), &mut frame) };
+    assert_eq!(status, CivStatus::InvalidFrame);
+
+    let status = unsafe { civ_parse_frame(ptr::null(), 0, &mut frame) };
+    assert_eq!(status, CivStatus::NullPointer);
+    let status = unsafe { civ_parse_frame(too_short.as_ptr(), 0, ptr::null_mut()) };
+    assert_eq!(status, CivStatus::NullPointer);
+}
+
+/// Run the Python ctypes example against the shared library.
+///
+/// Ignored by default: it needs `python3` and the shared library, which is
+/// not part of a normal build. Build the library with the same profile, then
+/// run the ignored test:
+///
+/// ```text
+/// cargo rustc -p civ-protocol --features ffi --crate-type cdylib
+/// cargo test -p civ-protocol --features ffi --test ffi -- --ignored
+/// ```
+///
+/// Set `CIV_PROTOCOL_LIB` to use a copy of the library built elsewhere.
+#[test]
+#[ignore = "needs python3 and the cdylib; see the doc comment"]
+fn test_python_ctypes_example() {
+    let lib = match std::env::var_os("CIV_PROTOCOL_LIB") {
+        Some(path) => PathBuf::from(path),
+        None => {
+            // The test binary lives in target/<profile>/deps/; `cargo rustc`
+            // leaves the library in target/<profile>/.
+            let exe = std::env::current_exe().unwrap();
+            let profile_dir = exe.parent().and_then(|deps| deps.parent()).unwrap();
+            profile_dir.join(format!(
+                "{}civ_protocol{}",
+                std::env::consts::DLL_PREFIX,
+                std::env::consts::DLL_SUFFIX
+            ))
+        }
+    };
+    assert!(lib.exists(), "{} not built", lib.display());
+    let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/ffi_ctypes.py");
+    let output = Command::new("python3")
+        .arg(&script)
+        .arg(&lib)
+        .output()
+        .unwrap_or_else(|e| panic!("cannot run python3: {e}"));
+    assert!(
+        output.status.success(),
+        "ctypes example failed:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
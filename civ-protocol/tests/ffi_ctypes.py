@@ -0,0 +1,96 @@
+"""Use the civ-protocol C ABI from Python via ctypes.
+
+Build the shared library with the `ffi` feature, then point this script at it:
+
+    cargo build -p civ-protocol --no-default-features --features ffi
+    python3 civ-protocol/tests/ffi_ctypes.py target/debug/libciv_protocol.so
+
+Memory rules: encode functions write into a buffer we own; frames filled in by
+civ_parse_frame own their `data` until civ_frame_free is called.
+"""
+
+import ctypes
+import sys
+
+CIV_OK = 0
+CIV_BUFFER_TOO_SMALL = -2
+CIV_INCOMPLETE = -4
+
+
+class CivFrame(ctypes.Structure):
+    _fields_ = [
+        ("dst", ctypes.c_uint8),
+        ("src", ctypes.c_uint8),
+        ("command", ctypes.c_uint8),
+        ("has_sub_command", ctypes.c_uint8),
+        ("sub_command", ctypes.c_uint8),
+        ("data", ctypes.POINTER(ctypes.c_uint8)),
+        ("data_len", ctypes.c_size_t),
+        ("consumed", ctypes.c_size_t),
+    ]
+
+
+def load(path):
+    lib = ctypes.CDLL(path)
+    lib.civ_encode_set_frequency.argtypes = [
+        ctypes.c_uint64,
+        ctypes.POINTER(ctypes.c_uint8),
+        ctypes.POINTER(ctypes.c_size_t),
+    ]
+    lib.civ_encode_set_frequency.restype = ctypes.c_int32
+    lib.civ_parse_frame.argtypes = [
+        ctypes.POINTER(ctypes.c_uint8),
+        ctypes.c_size_t,
+        ctypes.POINTER(CivFrame),
+    ]
+    lib.civ_parse_frame.restype = ctypes.c_int32
+    lib.civ_frame_free.argtypes = [ctypes.POINTER(CivFrame)]
+    lib.civ_frame_free.restype = None
+    return lib
+
+
+def encode_set_frequency(lib, hz):
+    buf = (ctypes.c_uint8 * 16)()
+    length = ctypes.c_size_t(len(buf))
+    status = lib.civ_encode_set_frequency(hz, buf, ctypes.byref(length))
+    assert status == CIV_OK, status
+    return bytes(buf[: length.value])
+
+
+def parse_frame(lib, data):
+    buf = (ctypes.c_uint8 * len(data)).from_buffer_copy(data)
+    frame = CivFrame()
+    status = lib.civ_parse_frame(buf, len(data), ctypes.byref(frame))
+    if status != CIV_OK:
+        return status, None
+    try:
+        payload = bytes(frame.data[: frame.data_len]) if frame.data_len else b""
+        sub = frame.sub_command if frame.has_sub_command else None
+        return status, (frame.command, sub, payload, frame.consumed)
+    finally:
+        lib.civ_frame_free(ctypes.byref(frame))
+
+
+def main():
+    lib = load(sys.argv[1])
+
+    frame = encode_set_frequency(lib, 145_000_000)
+    assert frame == bytes.fromhex("FEFEB4E0050000004501FD"), frame.hex()
+
+    small = (ctypes.c_uint8 * 4)()
+    length = ctypes.c_size_t(len(small))
+    status = lib.civ_encode_set_frequency(145_000_000, small, ctypes.byref(length))
+    assert status == CIV_BUFFER_TOO_SMALL and length.value == 11
+
+    status, parsed = parse_frame(lib, bytes.fromhex("FEFEE0B414010128FD"))
+    assert status == CIV_OK, status
+    assert parsed == (0x14, 0x01, b"\x01\x28", 9), parsed
+
+    status, _ = parse_frame(lib, bytes.fromhex("FEFEE0B403"))
+    assert status == CIV_INCOMPLETE, status
+
+    print("ok")
+
+
+if __name__ == "__main__":
+    main()
@@ -1,17 +1,23 @@
 use std::sync::mpsc as std_mpsc;
 use std::time::Instant;
 
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use civ_protocol::Frequency;
 use civ_protocol::OperatingMode;
+use civ_protocol::command::duplex_sub;
+use civ_protocol::gps::{self, GPS_MESSAGE_LEN};
 
-use crate::message::{RadioCommand, RadioEvent, RadioState, Vfo, VfoState};
+use crate::event::AppEvent;
+use crate::explorer::Explorer;
+use crate::message::{RadioCommand, RadioEvent, RadioRequest, RadioState, Vfo, VfoState};
+use crate::poll::PollWarnings;
 
 /// Severity level for log entries.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
     Info,
+    Warn,
     Error,
 }
 
@@ -26,6 +32,12 @@ pub enum Focus {
     RxTone,
     Power,
     Offset,
+    /// GPS row context; waits for a second key choosing what to edit.
+    Gps,
+    /// GPS message text prompt.
+    GpsMessage,
+    /// Raw Various/Setting explorer panel.
+    Explorer,
 }
 
 /// Tone type category for the first phase of tone editing.
@@ -83,15 +95,6 @@ pub enum PowerLevel {
 }
 
 impl PowerLevel {
-    /// All levels in order from lowest to highest.
-    pub const ALL: [PowerLevel; 5] = [
-        PowerLevel::SLow,
-        PowerLevel::Low1,
-        PowerLevel::Low2,
-        PowerLevel::Mid,
-        PowerLevel::High,
-    ];
-
     /// Raw CI-V value (midpoint of the range) for this power level.
     pub fn to_raw(self) -> u16 {
         match self {
@@ -157,17 +160,17 @@ impl DuplexDir {
     /// CI-V raw value for this direction.
     pub fn to_raw(self) -> u8 {
         match self {
-            Self::Simplex => 0x10,
-            Self::DupPlus => 0x12,
-            Self::DupMinus => 0x11,
+            Self::Simplex => duplex_sub::SIMPLEX,
+            Self::DupPlus => duplex_sub::PLUS,
+            Self::DupMinus => duplex_sub::MINUS,
         }
     }
 
     /// From CI-V raw duplex byte.
     pub fn from_raw(raw: u8) -> Self {
         match raw {
-            0x12 => Self::DupPlus,
-            0x11 => Self::DupMinus,
+            duplex_sub::PLUS => Self::DupPlus,
+            duplex_sub::MINUS => Self::DupMinus,
             _ => Self::Simplex,
         }
     }
@@ -274,6 +277,9 @@ pub struct App {
     pub input_mode: InputMode,
     pub connected: bool,
     pub error_log: Vec<(Instant, LogLevel, String)>,
+    /// Time of the event being handled. Log entries are stamped with it and
+    /// their age is shown relative to it, so a replay shows recorded times.
+    pub now: Instant,
     pub should_quit: bool,
     pub baud_rate: u32,
 
@@ -306,16 +312,29 @@ pub struct App {
     pub offset_edit_hz: u64,
     pub offset_cursor: usize,
 
-    cmd_tx: std_mpsc::Sender<RadioCommand>,
+    // GPS message edit state
+    pub gps_message_edit: String,
+    /// The radio's message has arrived or the user has edited the prompt.
+    /// Until then Enter would overwrite the radio's message with a blank.
+    pub gps_message_ready: bool,
+
+    /// Raw Various/Setting explorer; keeps its history while closed.
+    pub explorer: Explorer,
+
+    /// Fields currently failing to read, so each failure is logged once.
+    poll_warnings: PollWarnings,
+
+    cmd_tx: std_mpsc::Sender<RadioRequest>,
 }
 
 impl App {
-    pub fn new(cmd_tx: std_mpsc::Sender<RadioCommand>, baud_rate: u32) -> Self {
+    pub fn new(cmd_tx: std_mpsc::Sender<RadioRequest>, baud_rate: u32) -> Self {
         Self {
             radio_state: RadioState::default(),
             input_mode: InputMode::Normal,
             connected: false,
             error_log: Vec::new(),
+            now: Instant::now(),
             should_quit: false,
             baud_rate,
             current_vfo: Vfo::A,
@@ -335,10 +354,42 @@ impl App {
             duplex_dir_edit: DuplexDir::Simplex,
             offset_edit_hz: 600_000,
             offset_cursor: 0,
+            gps_message_edit: String::new(),
+            gps_message_ready: false,
+            explorer: Explorer::default(),
+            poll_warnings: PollWarnings::default(),
             cmd_tx,
         }
     }
 
+    /// Handle one event from the main loop as it arrives.
+    pub fn handle_event(&mut self, event: AppEvent) {
+        self.handle_event_at(event, Instant::now());
+    }
+
+    /// Handle one event that happened at `now`, live or replayed.
+    pub fn handle_event_at(&mut self, event: AppEvent, now: Instant) {
+        self.now = now;
+        match event {
+            AppEvent::Key(key) => {
+                // crossterm 0.28 sends both Press and Release on some platforms.
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+            AppEvent::Radio(radio_event) => {
+                self.handle_radio_event(radio_event);
+            }
+            AppEvent::Tick => {
+                // Tick just triggers a redraw (handled by the loop).
+            }
+            AppEvent::Resize(w, h) => {
+                // Terminal auto-resizes on next draw.
+                log::debug!("terminal resized to {w}x{h}");
+            }
+        }
+    }
+
     /// Handle a radio event from the radio task.
     pub fn handle_radio_event(&mut self, event: RadioEvent) {
         match event {
@@ -354,10 +405,36 @@ impl App {
                 self.radio_state = state;
             }
             RadioEvent::Error(msg) => {
-                self.error_log.push((Instant::now(), LogLevel::Error, msg));
+                self.error_log.push((self.now, LogLevel::Error, msg));
+            }
+            RadioEvent::Warning(msg) => {
+                self.error_log.push((self.now, LogLevel::Warn, msg));
             }
             RadioEvent::Info(msg) => {
-                self.error_log.push((Instant::now(), LogLevel::Info, msg));
+                self.error_log.push((self.now, LogLevel::Info, msg));
+            }
+            RadioEvent::GpsMessage(text) => {
+                // Prefill the prompt unless the user has already started typing.
+                if self.input_mode == InputMode::Editing(Focus::GpsMessage)
+                    && !self.gps_message_ready
+                {
+                    self.gps_message_edit = text;
+                    self.gps_message_ready = true;
+                }
+            }
+            RadioEvent::PollReport(report) => {
+                for warning in self.poll_warnings.update(&report) {
+                    self.error_log.push((self.now, LogLevel::Warn, warning));
+                }
+            }
+            RadioEvent::CommandResult(result) => {
+                if result.skipped {
+                    let msg = format!("{} skipped, radio already set", result.command);
+                    self.error_log.push((self.now, LogLevel::Info, msg));
+                }
+            }
+            RadioEvent::Raw(result) => {
+                self.explorer.push(result);
             }
             RadioEvent::Connected => {
                 self.connected = true;
@@ -393,11 +470,13 @@ impl App {
             KeyCode::Char('r') | KeyCode::Char('R') => self.enter_edit(Focus::RxTone),
             KeyCode::Char('p') | KeyCode::Char('P') => self.enter_edit(Focus::Power),
             KeyCode::Char('o') | KeyCode::Char('O') => self.enter_edit(Focus::Offset),
+            KeyCode::Char('g') | KeyCode::Char('G') => self.enter_edit(Focus::Gps),
             KeyCode::Char('w') | KeyCode::Char('W') => self.toggle_width(),
             KeyCode::Char('v') | KeyCode::Char('V') => self.toggle_vfo(),
             KeyCode::Char('+') | KeyCode::Char('=') => self.adjust_volume(1),
             KeyCode::Char('-') | KeyCode::Char('_') => self.adjust_volume(-1),
             KeyCode::Char('0') => self.toggle_mute(),
+            KeyCode::F(9) => self.enter_edit(Focus::Explorer),
             KeyCode::Left | KeyCode::Right | KeyCode::Up | KeyCode::Down => {
                 self.enter_edit(Focus::Frequency);
                 self.handle_freq_edit_key(key.code);
@@ -407,6 +486,12 @@ impl App {
     }
 
     fn handle_edit_key(&mut self, key: KeyEvent, focus: Focus) {
+        // A pending raw write takes every key as its answer.
+        if focus == Focus::Explorer && self.explorer.pending_write.is_some() {
+            self.handle_explorer_confirm_key(key.code);
+            return;
+        }
+
         // Pressing the same hotkey that entered edit mode cancels without saving.
         let cancel_key = matches!(
             (key.code, focus),
@@ -418,6 +503,8 @@ impl App {
                 | (KeyCode::Char('r') | KeyCode::Char('R'), Focus::RxTone)
                 | (KeyCode::Char('p') | KeyCode::Char('P'), Focus::Power)
                 | (KeyCode::Char('o') | KeyCode::Char('O'), Focus::Offset)
+                | (KeyCode::Char('g') | KeyCode::Char('G'), Focus::Gps)
+                | (KeyCode::F(9), Focus::Explorer)
         );
 
         match key.code {
@@ -443,6 +530,10 @@ impl App {
                     self.handle_tone_enter(focus);
                 } else if focus == Focus::Offset {
                     self.handle_offset_enter();
+                } else if focus == Focus::GpsMessage {
+                    self.handle_gps_message_enter();
+                } else if focus == Focus::Explorer {
+                    self.handle_explorer_enter();
                 } else {
                     self.confirm_edit(focus);
                     self.input_mode = InputMode::Normal;
@@ -456,6 +547,9 @@ impl App {
                 Focus::TxTone | Focus::RxTone => self.handle_tone_edit_key(key.code),
                 Focus::Power => self.handle_power_edit_key(key.code),
                 Focus::Offset => self.handle_offset_edit_key(key.code),
+                Focus::Gps => self.handle_gps_context_key(key.code),
+                Focus::GpsMessage => self.handle_gps_message_edit_key(key.code),
+                Focus::Explorer => self.handle_explorer_key(key.code),
             },
         }
     }
@@ -537,6 +631,14 @@ impl App {
                     dtcs_rx_pol != 0
                 };
             }
+            Focus::Gps => {}
+            Focus::GpsMessage => {
+                // The radio's current message arrives as a GpsMessage event.
+                self.gps_message_edit.clear();
+                self.gps_message_ready = false;
+                let _ = self.cmd_tx.send(RadioRequest::ReadGpsMessage);
+            }
+            Focus::Explorer => {}
         }
         self.input_mode = InputMode::Editing(focus);
     }
@@ -556,22 +658,16 @@ impl App {
             Focus::Power => RadioCommand::SetRfPower(self.power_edit.to_raw()),
             Focus::TxTone | Focus::RxTone => return, // handled by confirm_tone
             Focus::Offset => return,                 // handled by handle_offset_enter
+            Focus::Gps | Focus::GpsMessage => return, // handled by handle_gps_message_enter
+            Focus::Explorer => return,               // handled by handle_explorer_enter
         };
-        let _ = self.cmd_tx.send(cmd);
+        self.send(cmd);
     }
 
     fn handle_freq_edit_key(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Left => {
-                if self.freq_cursor > 0 {
-                    self.freq_cursor -= 1;
-                }
-            }
-            KeyCode::Right => {
-                if self.freq_cursor < 8 {
-                    self.freq_cursor += 1;
-                }
-            }
+            KeyCode::Left => self.freq_cursor = self.freq_cursor.saturating_sub(1),
+            KeyCode::Right => self.freq_cursor = (self.freq_cursor + 1).min(8),
             KeyCode::Up => {
                 let step = FREQ_DIGIT_POWERS[self.freq_cursor];
                 let new_hz = self.freq_edit_hz.saturating_add(step);
@@ -625,32 +721,16 @@ impl App {
 
     fn handle_volume_edit_key(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Up | KeyCode::Right => {
-                if self.af_edit < VOLUME_MAX_STEP {
-                    self.af_edit += 1;
-                }
-            }
-            KeyCode::Down | KeyCode::Left => {
-                if self.af_edit > 0 {
-                    self.af_edit -= 1;
-                }
-            }
+            KeyCode::Up | KeyCode::Right => self.af_edit = (self.af_edit + 1).min(VOLUME_MAX_STEP),
+            KeyCode::Down | KeyCode::Left => self.af_edit = self.af_edit.saturating_sub(1),
             _ => {}
         }
     }
 
     fn handle_level_edit_key(&mut self, code: KeyCode) {
         match code {
-            KeyCode::Up | KeyCode::Right => {
-                if self.sql_edit < 255 {
-                    self.sql_edit += 1;
-                }
-            }
-            KeyCode::Down | KeyCode::Left => {
-                if self.sql_edit > 0 {
-                    self.sql_edit -= 1;
-                }
-            }
+            KeyCode::Up | KeyCode::Right => self.sql_edit = (self.sql_edit + 1).min(255),
+            KeyCode::Down | KeyCode::Left => self.sql_edit = self.sql_edit.saturating_sub(1),
             _ => {}
         }
     }
@@ -679,16 +759,8 @@ impl App {
                 _ => {}
             },
             OffsetEditPhase::EditFrequency => match code {
-                KeyCode::Left => {
-                    if self.offset_cursor > 0 {
-                        self.offset_cursor -= 1;
-                    }
-                }
-                KeyCode::Right => {
-                    if self.offset_cursor < 7 {
-                        self.offset_cursor += 1;
-                    }
-                }
+                KeyCode::Left => self.offset_cursor = self.offset_cursor.saturating_sub(1),
+                KeyCode::Right => self.offset_cursor = (self.offset_cursor + 1).min(7),
                 KeyCode::Up => {
                     let step = OFFSET_DIGIT_POWERS[self.offset_cursor];
                     let new_hz = self.offset_edit_hz.saturating_add(step);
@@ -722,9 +794,7 @@ impl App {
             OffsetEditPhase::SelectDirection => {
                 if self.duplex_dir_edit == DuplexDir::Simplex {
                     // Simplex: just set the duplex direction, no offset needed.
-                    let _ = self
-                        .cmd_tx
-                        .send(RadioCommand::SetDuplex(self.duplex_dir_edit.to_raw()));
+                    self.send(RadioCommand::SetDuplex(self.duplex_dir_edit.to_raw()));
                     self.input_mode = InputMode::Normal;
                 } else {
                     // DUP+/DUP-: advance to offset frequency editing.
@@ -732,18 +802,94 @@ impl App {
                 }
             }
             OffsetEditPhase::EditFrequency => {
-                // Send both duplex direction and offset frequency.
-                let _ = self
-                    .cmd_tx
-                    .send(RadioCommand::SetDuplex(self.duplex_dir_edit.to_raw()));
-                let _ = self
-                    .cmd_tx
-                    .send(RadioCommand::SetOffset(self.offset_edit_hz));
+                // Send duplex direction and offset frequency in one shot.
+                self.send(RadioCommand::SetDuplexConfig {
+                    direction: self.duplex_dir_edit.to_raw(),
+                    offset_hz: self.offset_edit_hz,
+                });
                 self.input_mode = InputMode::Normal;
             }
         }
     }
 
+    fn handle_gps_context_key(&mut self, code: KeyCode) {
+        if let KeyCode::Char('m') | KeyCode::Char('M') = code {
+            self.enter_edit(Focus::GpsMessage);
+        }
+    }
+
+    fn handle_gps_message_edit_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Backspace => {
+                self.gps_message_edit.pop();
+                self.gps_message_ready = true;
+            }
+            // Characters are checked on Enter so the error can name them.
+            KeyCode::Char(c) if self.gps_message_edit.chars().count() < GPS_MESSAGE_LEN => {
+                self.gps_message_edit.push(c);
+                self.gps_message_ready = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Validate the GPS message locally; on error stay in the prompt so it can be fixed.
+    fn handle_gps_message_enter(&mut self) {
+        if !self.gps_message_ready {
+            self.error_log.push((
+                self.now,
+                LogLevel::Info,
+                "still reading the radio's GPS message".to_string(),
+            ));
+            return;
+        }
+        match gps::validate_gps_message(&self.gps_message_edit) {
+            Ok(()) => {
+                let text = self.gps_message_edit.clone();
+                self.send(RadioCommand::SetGpsMessage(text));
+                self.input_mode = InputMode::Normal;
+            }
+            Err(e) => {
+                self.error_log
+                    .push((self.now, LogLevel::Error, e.to_string()));
+            }
+        }
+    }
+
+    fn handle_explorer_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Backspace => {
+                self.explorer.input.pop();
+                self.explorer.status = None;
+            }
+            KeyCode::Char(c) if c.is_ascii_hexdigit() || c == ' ' || c == '=' => {
+                self.explorer.input.push(c);
+                self.explorer.status = None;
+            }
+            KeyCode::Up => self.explorer.scroll_back(1),
+            KeyCode::Down => self.explorer.scroll_forward(1),
+            KeyCode::PageUp => self.explorer.scroll_back(10),
+            KeyCode::PageDown => self.explorer.scroll_forward(10),
+            _ => {}
+        }
+    }
+
+    /// Send a read right away; a write waits for the confirmation prompt.
+    /// Input errors show in the explorer and the input is kept so it can be fixed.
+    fn handle_explorer_enter(&mut self) {
+        if let Some(request) = self.explorer.submit() {
+            let _ = self.cmd_tx.send(RadioRequest::Raw(request));
+        }
+    }
+
+    /// Only `y` sends the pending write; any other key drops it.
+    fn handle_explorer_confirm_key(&mut self, code: KeyCode) {
+        let accept = matches!(code, KeyCode::Char('y') | KeyCode::Char('Y'));
+        if let Some(request) = self.explorer.confirm(accept) {
+            let _ = self.cmd_tx.send(RadioRequest::Raw(request));
+        }
+    }
+
     fn handle_tone_edit_key(&mut self, code: KeyCode) {
         match self.tone_edit_phase {
             ToneEditPhase::SelectType => match code {
@@ -765,28 +911,16 @@ impl App {
             },
             ToneEditPhase::SelectValue => match self.tone_type_edit {
                 ToneType::Tpl => match code {
-                    KeyCode::Up => {
-                        if self.tone_freq_edit > 0 {
-                            self.tone_freq_edit -= 1;
-                        }
-                    }
+                    KeyCode::Up => self.tone_freq_edit = self.tone_freq_edit.saturating_sub(1),
                     KeyCode::Down => {
-                        if self.tone_freq_edit < CTCSS_TONES.len() - 1 {
-                            self.tone_freq_edit += 1;
-                        }
+                        self.tone_freq_edit = (self.tone_freq_edit + 1).min(CTCSS_TONES.len() - 1)
                     }
                     _ => {}
                 },
                 ToneType::Dpl => match code {
-                    KeyCode::Up => {
-                        if self.dtcs_code_edit > 0 {
-                            self.dtcs_code_edit -= 1;
-                        }
-                    }
+                    KeyCode::Up => self.dtcs_code_edit = self.dtcs_code_edit.saturating_sub(1),
                     KeyCode::Down => {
-                        if self.dtcs_code_edit < DTCS_CODES.len() - 1 {
-                            self.dtcs_code_edit += 1;
-                        }
+                        self.dtcs_code_edit = (self.dtcs_code_edit + 1).min(DTCS_CODES.len() - 1)
                     }
                     KeyCode::Left | KeyCode::Right => {
                         self.dtcs_pol_edit = !self.dtcs_pol_edit;
@@ -828,19 +962,19 @@ impl App {
             ToneType::Csq => {
                 // Determine the new tone_mode based on what the *other* side is doing.
                 let new_mode = compute_tone_mode(current_tone_mode, is_tx, ToneType::Csq);
-                let _ = self.cmd_tx.send(RadioCommand::SetToneMode(new_mode));
+                self.send(RadioCommand::SetToneMode(new_mode));
             }
             ToneType::Tpl => {
                 let freq = CTCSS_TONES[self.tone_freq_edit];
                 // Set the tone frequency first.
                 if is_tx {
-                    let _ = self.cmd_tx.send(RadioCommand::SetTxTone(freq));
+                    self.send(RadioCommand::SetTxTone(freq));
                 } else {
-                    let _ = self.cmd_tx.send(RadioCommand::SetRxTone(freq));
+                    self.send(RadioCommand::SetRxTone(freq));
                 }
                 // Then set the tone mode.
                 let new_mode = compute_tone_mode(current_tone_mode, is_tx, ToneType::Tpl);
-                let _ = self.cmd_tx.send(RadioCommand::SetToneMode(new_mode));
+                self.send(RadioCommand::SetToneMode(new_mode));
             }
             ToneType::Dpl => {
                 let code = DTCS_CODES[self.dtcs_code_edit];
@@ -851,12 +985,10 @@ impl App {
                 } else {
                     (current_tx_pol, pol)
                 };
-                let _ = self
-                    .cmd_tx
-                    .send(RadioCommand::SetDtcsCode(tx_pol, rx_pol, code));
+                self.send(RadioCommand::SetDtcsCode(tx_pol, rx_pol, code));
                 // Then set the tone mode.
                 let new_mode = compute_tone_mode(current_tone_mode, is_tx, ToneType::Dpl);
-                let _ = self.cmd_tx.send(RadioCommand::SetToneMode(new_mode));
+                self.send(RadioCommand::SetToneMode(new_mode));
             }
         }
     }
@@ -864,7 +996,7 @@ impl App {
     /// Toggle VFO A/B and send the command immediately.
     fn toggle_vfo(&mut self) {
         self.current_vfo = self.current_vfo.toggle();
-        let _ = self.cmd_tx.send(RadioCommand::SelectVfo(self.current_vfo));
+        self.send(RadioCommand::SelectVfo(self.current_vfo));
     }
 
     /// Toggle channel width (wide ↔ narrow) and send immediately.
@@ -872,7 +1004,7 @@ impl App {
         if let Some(mode) = self.active_vfo_state().mode {
             let new_mode = mode.toggle_width();
             if new_mode != mode {
-                let _ = self.cmd_tx.send(RadioCommand::SetMode(new_mode));
+                self.send(RadioCommand::SetMode(new_mode));
             }
         }
     }
@@ -886,9 +1018,7 @@ impl App {
             .unwrap_or(0);
         let new_step = (current as i16 + delta).clamp(0, VOLUME_MAX_STEP as i16) as u16;
         self.mute_restore_step = None;
-        let _ = self
-            .cmd_tx
-            .send(RadioCommand::SetAfLevel(volume_step_to_raw(new_step)));
+        self.send(RadioCommand::SetAfLevel(volume_step_to_raw(new_step)));
     }
 
     /// Toggle mute. Muting saves the current step and sets volume to 0.
@@ -896,9 +1026,7 @@ impl App {
     fn toggle_mute(&mut self) {
         if let Some(restore) = self.mute_restore_step.take() {
             // Unmute: restore previous volume.
-            let _ = self
-                .cmd_tx
-                .send(RadioCommand::SetAfLevel(volume_step_to_raw(restore)));
+            self.send(RadioCommand::SetAfLevel(volume_step_to_raw(restore)));
         } else {
             // Mute: save current volume, set to 0.
             let current = self
@@ -907,17 +1035,20 @@ impl App {
                 .map(raw_to_volume_step)
                 .unwrap_or(0);
             self.mute_restore_step = Some(current);
-            let _ = self
-                .cmd_tx
-                .send(RadioCommand::SetAfLevel(volume_step_to_raw(0)));
+            self.send(RadioCommand::SetAfLevel(volume_step_to_raw(0)));
         }
     }
 
     fn quit(&mut self) {
-        let _ = self.cmd_tx.send(RadioCommand::Quit);
+        let _ = self.cmd_tx.send(RadioRequest::Quit);
         self.should_quit = true;
     }
 
+    /// Ask the radio task to apply `cmd`.
+    fn send(&self, cmd: RadioCommand) {
+        let _ = self.cmd_tx.send(RadioRequest::Command(cmd));
+    }
+
     /// Get the 9 digits of the frequency for display.
     pub fn freq_digits(&self, hz: u64) -> [u8; 9] {
         let mut digits = [0u8; 9];
@@ -938,7 +1069,7 @@ impl App {
 }
 
 /// Determine the current ToneType for a given side (Tx or Rx) from the tone_mode byte.
-fn current_tone_type(tone_mode: u8, is_tx: bool) -> ToneType {
+pub fn current_tone_type(tone_mode: u8, is_tx: bool) -> ToneType {
     if is_tx {
         match tone_mode {
             0x01 | 0x09 => ToneType::Tpl,
@@ -987,3 +1118,58 @@ fn compute_tone_mode(current_mode: u8, is_tx: bool, new_type: ToneType) -> u8 {
         (ToneType::Tpl, ToneType::Dpl) => 0x09, // fallback: TPL+TPL (radio may not support TPL+DPL)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gps_message_prompt() -> (App, std_mpsc::Receiver<RadioRequest>) {
+        let (cmd_tx, cmd_rx) = std_mpsc::channel();
+        let mut app = App::new(cmd_tx, 9600);
+        app.enter_edit(Focus::GpsMessage);
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(RadioRequest::ReadGpsMessage)
+        ));
+        (app, cmd_rx)
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_event(AppEvent::Key(KeyEvent::new(code, KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn test_gps_message_enter_waits_for_read() {
+        let (mut app, cmd_rx) = gps_message_prompt();
+
+        // Nothing read and nothing typed: the radio's message must survive.
+        press(&mut app, KeyCode::Enter);
+        assert!(cmd_rx.try_recv().is_err());
+        assert_eq!(app.input_mode, InputMode::Editing(Focus::GpsMessage));
+
+        app.handle_radio_event(RadioEvent::GpsMessage("ON THE AIR".to_string()));
+        press(&mut app, KeyCode::Enter);
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(RadioRequest::Command(RadioCommand::SetGpsMessage(text))) if text == "ON THE AIR"
+        ));
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn test_gps_message_edit_allows_enter_before_read() {
+        let (mut app, cmd_rx) = gps_message_prompt();
+
+        press(&mut app, KeyCode::Char('Q'));
+        press(&mut app, KeyCode::Backspace);
+        // A late read must not replace what the user typed, even a cleared field.
+        app.handle_radio_event(RadioEvent::GpsMessage("ON THE AIR".to_string()));
+        assert_eq!(app.gps_message_edit, "");
+
+        press(&mut app, KeyCode::Enter);
+        assert!(matches!(
+            cmd_rx.try_recv(),
+            Ok(RadioRequest::Command(RadioCommand::SetGpsMessage(text))) if text.is_empty()
+        ));
+    }
+}
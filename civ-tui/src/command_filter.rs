@@ -0,0 +1,317 @@
+//! Skip Set commands that would leave the radio unchanged.
+//!
+//! Re-sending a value the radio already has wastes bus time and, on some
+//! sets, makes the squelch chirp. The filter compares each Set command with
+//! what the radio is expected to hold: the last poll of the active VFO and
+//! levels, with every write made since that poll applied on top. Unknown
+//! values never match, so a command is only skipped when the state is known.
+
+use civ_protocol::Frequency;
+use civ_protocol::command::{Command, duplex_sub, tone_sub};
+
+use crate::message::{RadioCommand, VfoState};
+
+/// What the radio is expected to hold right now.
+#[derive(Debug, Clone, Default)]
+struct Expected {
+    vfo: VfoState,
+    af_level: Option<u16>,
+    squelch: Option<u16>,
+}
+
+#[derive(Debug)]
+pub struct CommandFilter {
+    enabled: bool,
+    expected: Expected,
+}
+
+impl CommandFilter {
+    /// A disabled filter never skips anything (unconditional writes).
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            expected: Expected::default(),
+        }
+    }
+
+    /// Take a fresh poll of the active VFO as the expected state.
+    ///
+    /// Writes recorded before the poll are dropped: the poll already
+    /// reflects whatever the radio made of them.
+    pub fn polled(&mut self, vfo: &VfoState, af_level: Option<u16>, squelch: Option<u16>) {
+        self.expected = Expected {
+            vfo: vfo.clone(),
+            af_level,
+            squelch,
+        };
+    }
+
+    /// Forget the expected state until the next poll, e.g. after a raw
+    /// write that may have touched any of the polled fields.
+    pub fn forget(&mut self) {
+        self.expected = Expected::default();
+    }
+
+    /// Update the expected state after `cmd` was executed.
+    ///
+    /// A successful write sets the value it wrote; a failed one leaves the
+    /// touched fields unknown until the next poll.
+    pub fn record(&mut self, cmd: &RadioCommand, ok: bool) {
+        fn set<T>(field: &mut Option<T>, value: T, ok: bool) {
+            *field = ok.then_some(value);
+        }
+
+        let e = &mut self.expected;
+        match cmd {
+            RadioCommand::SetFrequency(freq) => set(&mut e.vfo.frequency, *freq, ok),
+            RadioCommand::SetMode(mode) => set(&mut e.vfo.mode, *mode, ok),
+            RadioCommand::SetAfLevel(level) => set(&mut e.af_level, *level, ok),
+            RadioCommand::SetSquelch(level) => set(&mut e.squelch, *level, ok),
+            RadioCommand::SetRfPower(level) => set(&mut e.vfo.rf_power, *level, ok),
+            RadioCommand::SetDuplex(dir) => set(&mut e.vfo.duplex, *dir, ok),
+            RadioCommand::SetDuplexConfig {
+                direction,
+                offset_hz,
+            } => {
+                set(&mut e.vfo.duplex, *direction, ok);
+                if *direction != duplex_sub::SIMPLEX {
+                    e.vfo.offset = Frequency::from_hz(*offset_hz).ok().filter(|_| ok);
+                }
+            }
+            RadioCommand::SetToneMode(mode) => set(&mut e.vfo.tone_mode, *mode, ok),
+            RadioCommand::SetTxTone(tenths) => set(&mut e.vfo.tx_tone_freq, *tenths, ok),
+            RadioCommand::SetRxTone(tenths) => set(&mut e.vfo.rx_tone_freq, *tenths, ok),
+            RadioCommand::SetDtcsCode(tx_pol, rx_pol, code) => {
+                set(&mut e.vfo.dtcs_tx_pol, *tx_pol, ok);
+                set(&mut e.vfo.dtcs_rx_pol, *rx_pol, ok);
+                set(&mut e.vfo.dtcs_code, *code, ok);
+            }
+            // A different VFO, or a power cycle, invalidates everything.
+            RadioCommand::SelectVfo(_) | RadioCommand::PowerOn | RadioCommand::PowerOff => {
+                *e = Expected::default();
+            }
+            RadioCommand::SetGpsMessage(_) => {}
+        }
+    }
+
+    /// Would `cmd` leave the radio unchanged?
+    ///
+    /// Frequencies, offsets and levels must match exactly; tones and DTCS
+    /// codes are compared by the bytes they encode to.
+    pub fn is_noop(&self, cmd: &RadioCommand) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let e = &self.expected;
+        let vfo = &e.vfo;
+        match cmd {
+            RadioCommand::SetFrequency(freq) => vfo.frequency == Some(*freq),
+            RadioCommand::SetMode(mode) => vfo.mode == Some(*mode),
+            RadioCommand::SetAfLevel(level) => e.af_level == Some(*level),
+            RadioCommand::SetSquelch(level) => e.squelch == Some(*level),
+            RadioCommand::SetRfPower(level) => vfo.rf_power == Some(*level),
+            RadioCommand::SetDuplex(dir) => vfo.duplex == Some(*dir),
+            RadioCommand::SetDuplexConfig {
+                direction,
+                offset_hz,
+            } => {
+                vfo.duplex == Some(*direction)
+                    && (*direction == duplex_sub::SIMPLEX
+                        || vfo.offset.map(|f| f.hz()) == Some(*offset_hz))
+            }
+            RadioCommand::SetToneMode(mode) => vfo.tone_mode == Some(*mode),
+            RadioCommand::SetTxTone(tenths) => same_tone(vfo.tx_tone_freq, *tenths),
+            RadioCommand::SetRxTone(tenths) => same_tone(vfo.rx_tone_freq, *tenths),
+            RadioCommand::SetDtcsCode(tx_pol, rx_pol, code) => {
+                match (vfo.dtcs_tx_pol, vfo.dtcs_rx_pol, vfo.dtcs_code) {
+                    (Some(tx), Some(rx), Some(current)) => {
+                        dtcs_bytes(tx, rx, current) == dtcs_bytes(*tx_pol, *rx_pol, *code)
+                    }
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+fn same_tone(current: Option<u16>, tenths: u16) -> bool {
+    current.is_some_and(|current| tone_bytes(current) == tone_bytes(tenths))
+}
+
+/// Tone frequency as sent on the wire.
+fn tone_bytes(tenths: u16) -> Option<Vec<u8>> {
+    Command::SetTone(tone_sub::REPEATER_TONE, tenths)
+        .to_frame()
+        .ok()
+        .map(|frame| frame.data)
+}
+
+/// DTCS code and polarity bytes exactly as `SetDtcs` sends them.
+fn dtcs_bytes(tx_pol: u8, rx_pol: u8, code: u16) -> Option<Vec<u8>> {
+    Command::SetDtcs(tx_pol, rx_pol, code)
+        .to_frame()
+        .ok()
+        .map(|frame| frame.data)
+}
+
+#[cfg(test)]
+mod tests {
+    use civ_protocol::OperatingMode;
+
+    use super::*;
+
+    fn freq(hz: u64) -> Frequency {
+        Frequency::from_hz(hz).unwrap()
+    }
+
+    /// A filter that has just polled a typical 2 m FM repeater setup.
+    fn polled_filter(enabled: bool) -> CommandFilter {
+        let vfo = VfoState {
+            frequency: Some(freq(146_940_000)),
+            mode: Some(OperatingMode::Fm),
+            rf_power: Some(128),
+            tone_mode: Some(0x01),
+            tx_tone_freq: Some(1000),
+            rx_tone_freq: Some(885),
+            dtcs_code: Some(23),
+            dtcs_tx_pol: Some(0),
+            dtcs_rx_pol: Some(1),
+            duplex: Some(duplex_sub::MINUS),
+            offset: Some(freq(600_000)),
+        };
+        let mut filter = CommandFilter::new(enabled);
+        filter.polled(&vfo, Some(100), Some(30));
+        filter
+    }
+
+    #[test]
+    fn test_skips_values_the_radio_already_has() {
+        let filter = polled_filter(true);
+        for cmd in [
+            RadioCommand::SetFrequency(freq(146_940_000)),
+            RadioCommand::SetMode(OperatingMode::Fm),
+            RadioCommand::SetAfLevel(100),
+            RadioCommand::SetSquelch(30),
+            RadioCommand::SetRfPower(128),
+            RadioCommand::SetDuplex(duplex_sub::MINUS),
+            RadioCommand::SetDuplexConfig {
+                direction: duplex_sub::MINUS,
+                offset_hz: 600_000,
+            },
+            RadioCommand::SetToneMode(0x01),
+            RadioCommand::SetTxTone(1000),
+            RadioCommand::SetRxTone(885),
+            RadioCommand::SetDtcsCode(0, 1, 23),
+        ] {
+            assert!(filter.is_noop(&cmd), "{cmd:?} should be skipped");
+        }
+    }
+
+    #[test]
+    fn test_sends_changes_and_unfilterable_commands() {
+        let filter = polled_filter(true);
+        for cmd in [
+            RadioCommand::SetFrequency(freq(146_940_001)),
+            RadioCommand::SetMode(OperatingMode::FmN),
+            RadioCommand::SetAfLevel(101),
+            RadioCommand::SetSquelch(29),
+            RadioCommand::SetRfPower(255),
+            RadioCommand::SetDuplex(duplex_sub::PLUS),
+            RadioCommand::SetDuplexConfig {
+                direction: duplex_sub::MINUS,
+                offset_hz: 5_000_000,
+            },
+            RadioCommand::SetToneMode(0x02),
+            RadioCommand::SetTxTone(1035),
+            RadioCommand::SetRxTone(1000),
+            RadioCommand::SetDtcsCode(1, 1, 23),
+            RadioCommand::SetDtcsCode(0, 1, 25),
+            RadioCommand::SetGpsMessage("QRV".into()),
+            RadioCommand::PowerOn,
+        ] {
+            assert!(!filter.is_noop(&cmd), "{cmd:?} should be sent");
+        }
+    }
+
+    #[test]
+    fn test_unknown_state_is_never_skipped() {
+        let mut filter = CommandFilter::new(true);
+        assert!(!filter.is_noop(&RadioCommand::SetFrequency(freq(146_520_000))));
+        assert!(!filter.is_noop(&RadioCommand::SetTxTone(1000)));
+        filter.polled(&VfoState::default(), None, None);
+        assert!(!filter.is_noop(&RadioCommand::SetAfLevel(0)));
+        assert!(!filter.is_noop(&RadioCommand::SetDtcsCode(0, 0, 23)));
+    }
+
+    #[test]
+    fn test_disabled_filter_never_skips() {
+        let filter = polled_filter(false);
+        assert!(!filter.is_noop(&RadioCommand::SetFrequency(freq(146_940_000))));
+        assert!(!filter.is_noop(&RadioCommand::SetAfLevel(100)));
+    }
+
+    #[test]
+    fn test_simplex_ignores_offset() {
+        let mut filter = polled_filter(true);
+        filter.record(&RadioCommand::SetDuplex(duplex_sub::SIMPLEX), true);
+        assert!(filter.is_noop(&RadioCommand::SetDuplexConfig {
+            direction: duplex_sub::SIMPLEX,
+            offset_hz: 5_000_000,
+        }));
+    }
+
+    #[test]
+    fn test_dtcs_compares_the_bytes_sent() {
+        let mut filter = polled_filter(true);
+        let mut vfo = filter.expected.vfo.clone();
+        vfo.dtcs_rx_pol = Some(0x01);
+        filter.polled(&vfo, None, None);
+        assert!(filter.is_noop(&RadioCommand::SetDtcsCode(0, 0x01, 23)));
+        // A different raw polarity byte is a different command on the wire.
+        assert!(!filter.is_noop(&RadioCommand::SetDtcsCode(0, 0x0F, 23)));
+    }
+
+    #[test]
+    fn test_pending_writes_override_the_last_poll() {
+        let mut filter = polled_filter(true);
+        let a = RadioCommand::SetFrequency(freq(146_940_000));
+        let b = RadioCommand::SetFrequency(freq(146_520_000));
+
+        // B was written but not polled yet: going back to A is a real change,
+        // and repeating B is not.
+        filter.record(&b, true);
+        assert!(!filter.is_noop(&a));
+        assert!(filter.is_noop(&b));
+
+        // A failed write leaves the value unknown, so nothing is skipped.
+        filter.record(&a, false);
+        assert!(!filter.is_noop(&a));
+        assert!(!filter.is_noop(&b));
+
+        // The next poll is the new truth.
+        let vfo = VfoState {
+            frequency: Some(freq(146_940_000)),
+            ..VfoState::default()
+        };
+        filter.polled(&vfo, None, None);
+        assert!(filter.is_noop(&a));
+        assert!(!filter.is_noop(&b));
+    }
+
+    #[test]
+    fn test_vfo_switch_forgets_expected_state() {
+        let mut filter = polled_filter(true);
+        filter.record(&RadioCommand::SelectVfo(crate::message::Vfo::B), true);
+        assert!(!filter.is_noop(&RadioCommand::SetFrequency(freq(146_940_000))));
+        assert!(!filter.is_noop(&RadioCommand::SetAfLevel(100)));
+    }
+
+    #[test]
+    fn test_raw_write_forgets_expected_state() {
+        let mut filter = polled_filter(true);
+        assert!(filter.is_noop(&RadioCommand::SetToneMode(0x01)));
+        filter.forget();
+        assert!(!filter.is_noop(&RadioCommand::SetToneMode(0x01)));
+    }
+}
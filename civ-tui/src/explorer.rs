@@ -0,0 +1,428 @@
+//! Raw Various (0x16) and menu Setting (0x1A 0x05) explorer, opened with F9.
+//!
+//! Used to map items the typed API does not cover yet: type a target, read
+//! its raw bytes, and see a decoded value when [`decode`] knows the item.
+//! Writes send arbitrary bytes to the radio, so they are held back until the
+//! operator confirms them explicitly.
+//!
+//! Input syntax, bytes in hex and the setting item in decimal as in Icom's
+//! manuals:
+//!
+//! ```text
+//! 16 5D              read Various sub 0x5D
+//! 1A 05 0123         read menu setting item 0123
+//! 16 5D = 01         write Various sub 0x5D (asks for confirmation)
+//! 1A 05 0123 = 00 42 write menu setting item 0123 (asks for confirmation)
+//! ```
+//!
+//! A write sends every byte after `=` as the value, for both targets.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use civ_protocol::command::{SETTING_ITEM_MAX, cmd, setting_sub, various_sub};
+
+use crate::app::{ToneType, current_tone_type};
+
+/// Results kept in the history list; older ones are dropped.
+const HISTORY_MAX: usize = 200;
+
+/// A raw item the explorer can read or write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RawTarget {
+    /// Various function (0x16) sub-command.
+    Various(u8),
+    /// Menu setting (0x1A 0x05) item number, 0–9999.
+    Setting(u16),
+}
+
+impl fmt::Display for RawTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Various(sub) => write!(f, "{:02X} {sub:02X}", cmd::VARIOUS),
+            Self::Setting(item) => {
+                write!(
+                    f,
+                    "{:02X} {:02X} {item:04}",
+                    cmd::SETTING,
+                    setting_sub::MENU
+                )
+            }
+        }
+    }
+}
+
+/// A parsed explorer input line, sent to the radio task as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawRequest {
+    Read(RawTarget),
+    Write(RawTarget, Vec<u8>),
+}
+
+/// How a raw read or write went.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RawOutcome {
+    /// The radio returned these value bytes.
+    Read(Vec<u8>),
+    /// The radio accepted a write of these bytes.
+    Written(Vec<u8>),
+    /// NG, timeout or a reply we could not parse.
+    Failed(String),
+}
+
+/// One explorer access, sent back by the radio task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawResult {
+    pub target: RawTarget,
+    pub outcome: RawOutcome,
+}
+
+/// Parse an explorer input line; see the module docs for the syntax.
+pub fn parse_request(input: &str) -> Result<RawRequest, String> {
+    let (target, value) = match input.split_once('=') {
+        Some((target, value)) => (target, Some(value)),
+        None => (input, None),
+    };
+    let target = parse_target(target)?;
+    match value {
+        None => Ok(RawRequest::Read(target)),
+        Some(value) => {
+            let bytes = parse_hex_bytes(value)?;
+            if bytes.is_empty() {
+                return Err("nothing to write after '='".to_string());
+            }
+            Ok(RawRequest::Write(target, bytes))
+        }
+    }
+}
+
+fn parse_target(input: &str) -> Result<RawTarget, String> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let byte = |word: &str| {
+        u8::from_str_radix(word, 16)
+            .ok()
+            .filter(|_| word.len() <= 2)
+    };
+    match words.as_slice() {
+        [command, sub] if byte(command) == Some(cmd::VARIOUS) => byte(sub)
+            .map(RawTarget::Various)
+            .ok_or_else(|| format!("bad Various sub-command {sub:?}, expected one hex byte")),
+        [command, sub, item]
+            if byte(command) == Some(cmd::SETTING) && byte(sub) == Some(setting_sub::MENU) =>
+        {
+            item.parse::<u16>()
+                .ok()
+                .filter(|item| *item <= SETTING_ITEM_MAX)
+                .map(RawTarget::Setting)
+                .ok_or_else(|| format!("bad setting item {item:?}, expected 0000-9999"))
+        }
+        [] => Err("type a target, e.g. \"16 5D\" or \"1A 05 0123\"".to_string()),
+        _ => Err(format!(
+            "unknown target {:?}, expected \"16 <sub>\" or \"1A 05 <item>\"",
+            input.trim()
+        )),
+    }
+}
+
+/// Hex bytes, either separated by spaces (`00 42`) or run together (`0042`).
+fn parse_hex_bytes(input: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("odd number of hex digits in {:?}", input.trim()));
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let byte: String = pair.iter().collect();
+            // from_str_radix alone would take a sign, e.g. "+1".
+            if !pair.iter().all(char::is_ascii_hexdigit) {
+                return Err(format!("bad hex byte {byte:?}"));
+            }
+            u8::from_str_radix(&byte, 16).map_err(|_| format!("bad hex byte {byte:?}"))
+        })
+        .collect()
+}
+
+/// Format bytes as space-separated hex, e.g. `00 42`.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Turns the raw value bytes of one item into a readable description.
+type Decoder = fn(&[u8]) -> Option<String>;
+
+/// Items with a known meaning. New entries here are the start of a typed
+/// setting in the protocol crate.
+const DECODERS: &[(RawTarget, Decoder)] = &[(
+    RawTarget::Various(various_sub::TONE_SQUELCH_FUNC),
+    decode_tone_squelch_func,
+)];
+
+/// Decode `value` if `target` has a typed mapping.
+pub fn decode(target: RawTarget, value: &[u8]) -> Option<String> {
+    let (_, decoder) = DECODERS.iter().find(|(t, _)| *t == target)?;
+    decoder(value)
+}
+
+fn decode_tone_squelch_func(value: &[u8]) -> Option<String> {
+    let [mode] = value else { return None };
+    let side = |is_tx| match current_tone_type(*mode, is_tx) {
+        ToneType::Csq => "off".to_string(),
+        other => other.to_string(),
+    };
+    Some(format!("tone mode: Tx {}, Rx {}", side(true), side(false)))
+}
+
+/// Explorer panel state.
+#[derive(Debug, Default)]
+pub struct Explorer {
+    pub input: String,
+    /// Results, oldest first.
+    pub history: Vec<RawResult>,
+    /// Entries scrolled back from the newest one.
+    pub scroll: usize,
+    /// A write waiting for the operator to confirm it.
+    pub pending_write: Option<(RawTarget, Vec<u8>)>,
+    /// Why the last input was not sent: a parse error or a cancelled write.
+    /// Shown under the prompt until the next submit or edit.
+    pub status: Option<String>,
+}
+
+impl Explorer {
+    /// Parse the input line. A read is returned to send right away; a write
+    /// only becomes [`Self::pending_write`] and is sent by [`Self::confirm`].
+    /// Input errors go to [`Self::status`].
+    pub fn submit(&mut self) -> Option<RawRequest> {
+        self.status = None;
+        match parse_request(&self.input) {
+            Ok(RawRequest::Write(target, value)) => {
+                self.pending_write = Some((target, value));
+                None
+            }
+            Ok(read) => Some(read),
+            Err(e) => {
+                self.status = Some(e);
+                None
+            }
+        }
+    }
+
+    /// Answer the confirmation prompt. Only an explicit yes sends the write.
+    pub fn confirm(&mut self, accept: bool) -> Option<RawRequest> {
+        let (target, value) = self.pending_write.take()?;
+        if !accept {
+            self.status = Some(format!("write to {target} cancelled"));
+            return None;
+        }
+        Some(RawRequest::Write(target, value))
+    }
+
+    /// Add a result and jump back to the newest entry.
+    pub fn push(&mut self, result: RawResult) {
+        self.history.push(result);
+        if self.history.len() > HISTORY_MAX {
+            self.history.remove(0);
+        }
+        self.scroll = 0;
+    }
+
+    pub fn scroll_back(&mut self, lines: usize) {
+        self.scroll = (self.scroll + lines).min(self.history.len().saturating_sub(1));
+    }
+
+    pub fn scroll_forward(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explorer(input: &str) -> Explorer {
+        Explorer {
+            input: input.to_string(),
+            ..Explorer::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_reads() {
+        assert_eq!(
+            parse_request("16 5D"),
+            Ok(RawRequest::Read(RawTarget::Various(0x5D)))
+        );
+        assert_eq!(
+            parse_request("  16   5d "),
+            Ok(RawRequest::Read(RawTarget::Various(0x5D)))
+        );
+        assert_eq!(
+            parse_request("1A 05 0123"),
+            Ok(RawRequest::Read(RawTarget::Setting(123)))
+        );
+        assert_eq!(
+            parse_request("1a 05 7"),
+            Ok(RawRequest::Read(RawTarget::Setting(7)))
+        );
+    }
+
+    #[test]
+    fn test_parse_writes() {
+        assert_eq!(
+            parse_request("16 5D = 01"),
+            Ok(RawRequest::Write(RawTarget::Various(0x5D), vec![0x01]))
+        );
+        assert_eq!(
+            parse_request("16 5D = 01 02"),
+            Ok(RawRequest::Write(
+                RawTarget::Various(0x5D),
+                vec![0x01, 0x02]
+            ))
+        );
+        assert_eq!(
+            parse_request("1A 05 0123=0042"),
+            Ok(RawRequest::Write(RawTarget::Setting(123), vec![0x00, 0x42]))
+        );
+        assert_eq!(
+            parse_request("1A 05 0123 = 00 42 ff"),
+            Ok(RawRequest::Write(
+                RawTarget::Setting(123),
+                vec![0x00, 0x42, 0xFF]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        for input in [
+            "",
+            "16",
+            "16 5D0",
+            "16 xx",
+            "1A 06 0001",
+            "1A 05 10000",
+            "1A 05 -1",
+            "14 01",
+            "16 5D =",
+            "1A 05 0001 = 123",
+            "1A 05 0001 = zz",
+            "1A 05 0001 = +1",
+            "1A 05 0001 = é0",
+            "1A 05 0001 = 0é",
+            "1A 05 0001 = ééé",
+        ] {
+            assert!(
+                parse_request(input).is_err(),
+                "{input:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_target_display_round_trips() {
+        for target in [RawTarget::Various(0x5D), RawTarget::Setting(42)] {
+            assert_eq!(
+                parse_request(&target.to_string()),
+                Ok(RawRequest::Read(target))
+            );
+        }
+        assert_eq!(RawTarget::Setting(42).to_string(), "1A 05 0042");
+    }
+
+    #[test]
+    fn test_read_is_sent_immediately() {
+        let mut ex = explorer("1A 05 0123");
+        assert_eq!(ex.submit(), Some(RawRequest::Read(RawTarget::Setting(123))));
+        assert_eq!(ex.pending_write, None);
+    }
+
+    #[test]
+    fn test_write_waits_for_confirmation() {
+        let mut ex = explorer("16 5D = 01");
+        assert!(ex.submit().is_none());
+        assert_eq!(
+            ex.pending_write,
+            Some((RawTarget::Various(0x5D), vec![0x01]))
+        );
+        assert_eq!(
+            ex.confirm(true),
+            Some(RawRequest::Write(RawTarget::Various(0x5D), vec![0x01]))
+        );
+        // Confirming again does nothing: each write needs its own yes.
+        assert!(ex.confirm(true).is_none());
+    }
+
+    #[test]
+    fn test_declined_write_is_dropped() {
+        let mut ex = explorer("1A 05 0001 = 00");
+        assert!(ex.submit().is_none());
+        assert!(ex.confirm(false).is_none());
+        assert_eq!(ex.pending_write, None);
+        assert_eq!(ex.status.as_deref(), Some("write to 1A 05 0001 cancelled"));
+        assert!(ex.confirm(true).is_none());
+    }
+
+    #[test]
+    fn test_invalid_write_is_never_pending() {
+        let mut ex = explorer("16 5D = 1");
+        assert!(ex.submit().is_none());
+        assert_eq!(ex.pending_write, None);
+        assert_eq!(
+            ex.status.as_deref(),
+            Some("odd number of hex digits in \"1\"")
+        );
+
+        // The next good submit clears the error.
+        ex.input = "16 5D".to_string();
+        assert!(ex.submit().is_some());
+        assert_eq!(ex.status, None);
+    }
+
+    #[test]
+    fn test_decode_known_items_only() {
+        let tone = RawTarget::Various(various_sub::TONE_SQUELCH_FUNC);
+        assert_eq!(
+            decode(tone, &[0x09]).as_deref(),
+            Some("tone mode: Tx TSQL, Rx TSQL")
+        );
+        assert_eq!(
+            decode(tone, &[0x00]).as_deref(),
+            Some("tone mode: Tx off, Rx off")
+        );
+        assert_eq!(decode(tone, &[0x00, 0x01]), None);
+        assert_eq!(decode(RawTarget::Various(0x02), &[0x01]), None);
+        assert_eq!(decode(RawTarget::Setting(1), &[0x01]), None);
+    }
+
+    #[test]
+    fn test_history_is_capped_and_scrolls() {
+        let mut ex = Explorer::default();
+        for i in 0..HISTORY_MAX + 5 {
+            ex.push(RawResult {
+                target: RawTarget::Setting(i as u16),
+                outcome: RawOutcome::Read(vec![0x00]),
+            });
+        }
+        assert_eq!(ex.history.len(), HISTORY_MAX);
+        assert_eq!(ex.history[0].target, RawTarget::Setting(5));
+
+        ex.scroll_back(10);
+        assert_eq!(ex.scroll, 10);
+        ex.scroll_back(usize::MAX / 2);
+        assert_eq!(ex.scroll, HISTORY_MAX - 1);
+        ex.scroll_forward(5);
+        assert_eq!(ex.scroll, HISTORY_MAX - 6);
+
+        // A new result jumps back to the newest entry.
+        ex.push(RawResult {
+            target: RawTarget::Various(0x5D),
+            outcome: RawOutcome::Failed("radio returned NG (command rejected)".into()),
+        });
+        assert_eq!(ex.scroll, 0);
+    }
+}
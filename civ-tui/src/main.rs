@@ -1,14 +1,22 @@
 mod app;
+mod command_filter;
 mod event;
+mod explorer;
 mod message;
+mod ng_hint;
+mod poll;
 mod radio_task;
+mod session;
 mod ui;
 
-use std::io;
+use std::env;
+use std::io::{self, Stdout};
 use std::panic;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc as std_mpsc;
 
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use civ_protocol::Radio;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind};
 use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
@@ -17,14 +25,82 @@ use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
 use tokio::sync::mpsc as tokio_mpsc;
 
-use civ_protocol::Radio;
 use crate::app::App;
-use crate::event::{AppEvent, EventHandler};
+use crate::command_filter::CommandFilter;
+use crate::event::EventHandler;
 use crate::message::RadioEvent;
+use crate::session::Recorder;
+
+const USAGE: &str = "\
+usage: civ-tui [--ui-record <file>] [--always-write]
+       civ-tui --ui-replay <file> [--headless]
+
+  --ui-record <file>  record every UI event to <file> (JSON lines)
+  --always-write      send every Set command, even when the radio already
+                      has the requested value
+  --ui-replay <file>  replay a recording instead of connecting to the radio;
+                      press any key to exit once it finishes
+  --headless          with --ui-replay, render off-screen and print the final frame";
+
+/// Command-line options.
+#[derive(Debug, Default)]
+struct Options {
+    record: Option<PathBuf>,
+    replay: Option<PathBuf>,
+    headless: bool,
+    always_write: bool,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ui-record" => {
+                let path = args.next().ok_or("--ui-record needs a file")?;
+                options.record = Some(path.into());
+            }
+            "--ui-replay" => {
+                let path = args.next().ok_or("--ui-replay needs a file")?;
+                options.replay = Some(path.into());
+            }
+            "--headless" => options.headless = true,
+            "--always-write" => options.always_write = true,
+            "-h" | "--help" => return Err(String::new()),
+            other => return Err(format!("unknown argument: {other}")),
+        }
+    }
+    if options.headless && options.replay.is_none() {
+        return Err("--headless only applies to --ui-replay".to_string());
+    }
+    if options.record.is_some() && options.replay.is_some() {
+        return Err("--ui-record and --ui-replay are mutually exclusive".to_string());
+    }
+    Ok(options)
+}
 
 fn main() {
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
 
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(options) => options,
+        Err(msg) => {
+            if !msg.is_empty() {
+                eprintln!("{msg}");
+            }
+            eprintln!("{USAGE}");
+            std::process::exit(2);
+        }
+    };
+
+    if let Some(path) = &options.replay {
+        if let Err(e) = run_replay(path, options.headless) {
+            eprintln!("Replay of {} failed: {e}", path.display());
+            std::process::exit(1);
+        }
+        return;
+    }
+
     // Connect to radio in plain sync context (before tokio runtime starts).
     println!("CI-V Controller for ICOM ID-52A Plus");
     println!("=====================================");
@@ -54,6 +130,7 @@ fn main() {
     };
 
     let baud_rate = radio.baud_rate();
+    let filter = CommandFilter::new(!options.always_write);
 
     // Start tokio runtime for the TUI.
     let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime");
@@ -66,11 +143,11 @@ fn main() {
 
         // Spawn blocking radio task.
         tokio::task::spawn_blocking(move || {
-            radio_task::radio_loop(radio, cmd_rx, radio_event_tx);
+            radio_task::radio_loop(radio, cmd_rx, radio_event_tx, filter);
         });
 
         // Run the TUI.
-        if let Err(e) = run_tui(cmd_tx, radio_event_rx, baud_rate).await {
+        if let Err(e) = run_tui(cmd_tx, radio_event_rx, baud_rate, options.record).await {
             eprintln!("TUI error: {e}");
             std::process::exit(1);
         }
@@ -78,25 +155,33 @@ fn main() {
 }
 
 async fn run_tui(
-    cmd_tx: std_mpsc::Sender<crate::message::RadioCommand>,
+    cmd_tx: std_mpsc::Sender<crate::message::RadioRequest>,
     radio_event_rx: tokio_mpsc::UnboundedReceiver<RadioEvent>,
     baud_rate: u32,
+    record: Option<PathBuf>,
 ) -> io::Result<()> {
-    // Setup terminal.
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let mut terminal = setup_terminal()?;
+    // Restore the terminal however the loop ends, then report its error.
+    let result = event_loop(&mut terminal, cmd_tx, radio_event_rx, baud_rate, record).await;
+    let restored = restore_terminal(&mut terminal);
+    result.and(restored)
+}
 
-    // Install panic hook to restore terminal on panic.
-    let original_hook = panic::take_hook();
-    panic::set_hook(Box::new(move |info| {
-        let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
-        original_hook(info);
-    }));
+async fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    cmd_tx: std_mpsc::Sender<crate::message::RadioRequest>,
+    radio_event_rx: tokio_mpsc::UnboundedReceiver<RadioEvent>,
+    baud_rate: u32,
+    record: Option<PathBuf>,
+) -> io::Result<()> {
+    let mut recorder = match record {
+        Some(path) => Some(Recorder::create(
+            &path,
+            baud_rate,
+            crossterm::terminal::size()?,
+        )?),
+        None => None,
+    };
 
     let mut app = App::new(cmd_tx, baud_rate);
     let mut events = EventHandler::new(radio_event_rx);
@@ -106,23 +191,10 @@ async fn run_tui(
         terminal.draw(|frame| ui::draw(frame, &app))?;
 
         if let Some(event) = events.next().await {
-            match event {
-                AppEvent::Key(key) => {
-                    // crossterm 0.28 sends both Press and Release on some platforms.
-                    if key.kind == crossterm::event::KeyEventKind::Press {
-                        app.handle_key(key);
-                    }
-                }
-                AppEvent::Radio(radio_event) => {
-                    app.handle_radio_event(radio_event);
-                }
-                AppEvent::Tick => {
-                    // Tick just triggers a redraw (handled by the loop).
-                }
-                AppEvent::Resize(_, _) => {
-                    // Terminal auto-resizes on next draw.
-                }
+            if let Some(recorder) = recorder.as_mut() {
+                recorder.record(&event)?;
             }
+            app.handle_event(event);
         }
 
         if app.should_quit {
@@ -130,14 +202,71 @@ async fn run_tui(
         }
     }
 
-    // Restore terminal.
+    if let Some(recorder) = recorder {
+        recorder.finish()?;
+    }
+    Ok(())
+}
+
+/// Replay a recorded session, on screen or headless.
+fn run_replay(path: &Path, headless: bool) -> io::Result<()> {
+    let entries = session::load(path)?;
+
+    if headless {
+        let buffer = session::replay_headless(entries)?;
+        print!("{}", session::buffer_to_text(&buffer));
+        return Ok(());
+    }
+
+    let mut terminal = setup_terminal()?;
+    let result = session::replay(&mut terminal, entries, true).and_then(|app| {
+        // Keep the final frame on screen until a key is pressed, unless the
+        // recording itself ended with a quit.
+        if !app.should_quit {
+            wait_for_key()?;
+        }
+        Ok(())
+    });
+    restore_terminal(&mut terminal)?;
+    result
+}
+
+fn wait_for_key() -> io::Result<()> {
+    loop {
+        if let Event::Key(key) = crossterm::event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            return Ok(());
+        }
+    }
+}
+
+/// Enter raw mode and the alternate screen, restoring both on panic.
+fn setup_terminal() -> io::Result<Terminal<CrosstermBackend<Stdout>>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let terminal = Terminal::new(backend)?;
+
+    // Install panic hook to restore terminal on panic.
+    let original_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+        original_hook(info);
+    }));
+
+    Ok(terminal)
+}
+
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> io::Result<()> {
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
         DisableMouseCapture
     )?;
-    terminal.show_cursor()?;
-
-    Ok(())
+    terminal.show_cursor()
 }
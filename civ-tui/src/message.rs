@@ -1,10 +1,29 @@
+use serde::{Deserialize, Serialize};
+
 use civ_protocol::Frequency;
 use civ_protocol::OperatingMode;
+use civ_protocol::command::duplex_sub;
+
+use crate::explorer::{RawRequest, RawResult};
+use crate::poll::PollReport;
 
 // Domain types re-exported from the protocol library.
 pub use civ_protocol::{GpsPosition, RadioState, Vfo, VfoState};
 
-/// Commands sent from the TUI to the radio task.
+/// Requests sent from the TUI to the radio task.
+#[derive(Debug)]
+pub enum RadioRequest {
+    /// Apply a command to the radio.
+    Command(RadioCommand),
+    /// Read the GPS message; answered with `RadioEvent::GpsMessage`.
+    ReadGpsMessage,
+    /// Raw explorer read or write; answered with `RadioEvent::Raw`. Writes
+    /// are only sent after the explorer's confirmation prompt.
+    Raw(RawRequest),
+    Quit,
+}
+
+/// Commands the radio task applies to the radio.
 #[derive(Debug)]
 pub enum RadioCommand {
     SetFrequency(Frequency),
@@ -14,10 +33,16 @@ pub enum RadioCommand {
     SelectVfo(Vfo),
     /// Set RF power level (raw 0–255).
     SetRfPower(u16),
-    /// Set duplex direction (0x10=Simplex, 0x11=DUP-, 0x12=DUP+).
+    /// Set duplex direction (see `civ_protocol::command::duplex_sub`).
     SetDuplex(u8),
-    /// Set duplex offset frequency in Hz.
-    SetOffset(u64),
+    /// Set duplex direction and offset together.
+    ///
+    /// The offset is written first and the direction last, so a rejected
+    /// offset never leaves DUP+/DUP- active with a stale offset.
+    SetDuplexConfig {
+        direction: u8,
+        offset_hz: u64,
+    },
     /// Set the tone/squelch function mode (0x00–0x09).
     SetToneMode(u8),
     /// Set Tx tone frequency (tenths of Hz, e.g. 1318 = 131.8 Hz).
@@ -26,19 +51,62 @@ pub enum RadioCommand {
     SetRxTone(u16),
     /// Set DTCS code and polarity (tx_pol, rx_pol, code).
     SetDtcsCode(u8, u8, u16),
+    /// Set the GPS message (up to 20 printable ASCII characters).
+    SetGpsMessage(String),
     /// Power on the radio (with wake-up preamble).
+    #[allow(dead_code)] // not bound to a key yet
     PowerOn,
     /// Power off the radio.
+    #[allow(dead_code)] // not bound to a key yet
     PowerOff,
-    Quit,
+}
+
+impl RadioCommand {
+    /// Short operator-facing name for the command.
+    pub fn label(&self) -> String {
+        match self {
+            Self::SetFrequency(f) => format!("SetFrequency({:.3} MHz)", f.mhz()),
+            Self::SetMode(mode) => format!("SetMode({mode})"),
+            Self::SetDuplex(dir) | Self::SetDuplexConfig { direction: dir, .. } => {
+                let dir = match *dir {
+                    duplex_sub::MINUS => "DUP-",
+                    duplex_sub::PLUS => "DUP+",
+                    _ => "Simplex",
+                };
+                format!("SetDuplex({dir})")
+            }
+            Self::SetToneMode(mode) => format!("SetToneMode({mode:#04x})"),
+            Self::SetTxTone(tenths) => format!("SetTxTone({:.1} Hz)", *tenths as f64 / 10.0),
+            Self::SetRxTone(tenths) => format!("SetRxTone({:.1} Hz)", *tenths as f64 / 10.0),
+            Self::SetDtcsCode(_, _, code) => format!("SetDtcsCode({code:03})"),
+            other => format!("{other:?}"),
+        }
+    }
+}
+
+/// Outcome of a command that did not fail.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandResult {
+    /// Short name of the command, e.g. `SetFrequency(146.520 MHz)`.
+    pub command: String,
+    /// The radio already had the requested value, so nothing was sent.
+    pub skipped: bool,
 }
 
 /// Events sent from the radio task to the TUI.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RadioEvent {
     StateUpdate(RadioState),
     Error(String),
+    Warning(String),
     Info(String),
+    /// The GPS message as read from the radio.
+    GpsMessage(String),
+    /// Per-field outcomes of the latest poll; sent only when they change.
+    PollReport(PollReport),
+    CommandResult(CommandResult),
+    /// Result of a raw explorer read or write.
+    Raw(RawResult),
     Connected,
     Disconnected,
 }
@@ -0,0 +1,352 @@
+//! Operator-facing explanations for commands the radio rejects with NG.
+//!
+//! The radio's NG carries no reason. Most rejections in practice come from a
+//! handful of states (a receive-only frequency, a duplex offset that leaves
+//! the band, ...), so a table of known causes is matched against the
+//! rejected command and the active VFO's last poll. Unknown causes fall back
+//! to the plain error message.
+
+use civ_protocol::band::{RX_COVERAGE, tx_band};
+use civ_protocol::command::duplex_sub;
+use civ_protocol::{CivError, Frequency, OperatingMode};
+
+use crate::message::{RadioCommand, VfoState};
+
+/// The write of a `SetDuplexConfig` the radio refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplexStep {
+    /// The offset frequency write.
+    Offset,
+    /// The duplex direction write.
+    Direction,
+}
+
+impl DuplexStep {
+    /// Short name of the write for error messages.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Offset => "offset",
+            Self::Direction => "direction",
+        }
+    }
+}
+
+/// A likely cause for an NG, recognised from the rejected command and the
+/// active VFO's last polled state.
+struct NgHint {
+    /// Does this entry apply to the rejected command at all?
+    command: fn(&RadioCommand) -> bool,
+    /// Is the state one the radio is known to reject the command in?
+    ///
+    /// Unknown state (`None` fields) must evaluate to `false`.
+    predicate: fn(&RadioCommand, &VfoState) -> bool,
+    /// Operator-facing explanation; only called when `predicate` holds.
+    hint: fn(&RadioCommand, &VfoState) -> String,
+}
+
+const HINTS: &[NgHint] = &[
+    NgHint {
+        command: |cmd| matches!(cmd, RadioCommand::SetFrequency(_)),
+        predicate: |cmd, _| {
+            requested_frequency(cmd).is_some_and(|f| !RX_COVERAGE.contains(&f.hz()))
+        },
+        hint: |cmd, _| {
+            format!(
+                "{} MHz is outside the radio's receive coverage",
+                requested_frequency(cmd).map(mhz).unwrap_or_default()
+            )
+        },
+    },
+    NgHint {
+        command: |cmd| matches!(cmd, RadioCommand::SetMode(OperatingMode::Dv)),
+        predicate: |_, vfo| vfo.frequency.is_some_and(|f| tx_band(f.hz()).is_none()),
+        hint: |_, vfo| {
+            format!(
+                "current frequency {} MHz is outside DV coverage (2 m and 70 cm only)",
+                vfo.frequency.map(mhz).unwrap_or_default()
+            )
+        },
+    },
+    NgHint {
+        command: |cmd| {
+            matches!(
+                cmd,
+                RadioCommand::SetToneMode(_)
+                    | RadioCommand::SetTxTone(_)
+                    | RadioCommand::SetRxTone(_)
+                    | RadioCommand::SetDtcsCode(..)
+            )
+        },
+        predicate: |_, vfo| vfo.mode == Some(OperatingMode::Dv),
+        hint: |_, _| "tone and DTCS settings are not available in DV mode".to_string(),
+    },
+    NgHint {
+        command: |cmd| duplex_direction(cmd).is_some_and(is_duplex),
+        predicate: |_, vfo| vfo.frequency.is_some_and(|f| tx_band(f.hz()).is_none()),
+        hint: |_, vfo| {
+            format!(
+                "current frequency {} MHz is receive-only, so duplex is unavailable",
+                vfo.frequency.map(mhz).unwrap_or_default()
+            )
+        },
+    },
+    NgHint {
+        command: |cmd| duplex_direction(cmd).is_some_and(is_duplex),
+        predicate: |cmd, vfo| offset_tx_outside_band(cmd, vfo).is_some(),
+        hint: |cmd, vfo| {
+            let (band, tx_hz) = offset_tx_outside_band(cmd, vfo).unwrap_or_default();
+            format!(
+                "offset would put the transmit frequency at {:.3} MHz, outside the {band} band",
+                tx_hz as f64 / 1_000_000.0
+            )
+        },
+    },
+];
+
+/// Explain an NG for `cmd` using the active VFO's state.
+///
+/// Returns `None` for errors other than NG and for NGs no table entry
+/// recognises, so the caller falls back to the plain error message.
+pub fn explain(cmd: &RadioCommand, vfo: &VfoState, error: &CivError) -> Option<String> {
+    explain_as(cmd, &cmd.label(), vfo, error)
+}
+
+/// Explain an NG for one write of a `SetDuplexConfig`, naming the write
+/// the radio rejected rather than the whole command.
+pub fn explain_duplex_step(
+    cmd: &RadioCommand,
+    step: DuplexStep,
+    vfo: &VfoState,
+    error: &CivError,
+) -> Option<String> {
+    let RadioCommand::SetDuplexConfig {
+        direction,
+        offset_hz,
+    } = cmd
+    else {
+        return explain(cmd, vfo, error);
+    };
+    let set_duplex = RadioCommand::SetDuplex(*direction).label();
+    let label = match step {
+        DuplexStep::Offset => format!(
+            "SetOffset({:.3} MHz) for {set_duplex}",
+            *offset_hz as f64 / 1_000_000.0
+        ),
+        DuplexStep::Direction => set_duplex,
+    };
+    explain_as(cmd, &label, vfo, error)
+}
+
+fn explain_as(cmd: &RadioCommand, label: &str, vfo: &VfoState, error: &CivError) -> Option<String> {
+    if !matches!(error, CivError::Ng) {
+        return None;
+    }
+    let entry = HINTS
+        .iter()
+        .find(|entry| (entry.command)(cmd) && (entry.predicate)(cmd, vfo))?;
+    Some(format!(
+        "radio rejected {label} \u{2014} {}",
+        (entry.hint)(cmd, vfo)
+    ))
+}
+
+fn mhz(f: Frequency) -> String {
+    format!("{:.3}", f.mhz())
+}
+
+fn requested_frequency(cmd: &RadioCommand) -> Option<Frequency> {
+    match cmd {
+        RadioCommand::SetFrequency(f) => Some(*f),
+        _ => None,
+    }
+}
+
+/// Duplex direction requested by a command, if it sets one.
+fn duplex_direction(cmd: &RadioCommand) -> Option<u8> {
+    match cmd {
+        RadioCommand::SetDuplex(dir) | RadioCommand::SetDuplexConfig { direction: dir, .. } => {
+            Some(*dir)
+        }
+        _ => None,
+    }
+}
+
+fn is_duplex(dir: u8) -> bool {
+    matches!(dir, duplex_sub::MINUS | duplex_sub::PLUS)
+}
+
+/// For a duplex command on a transmit band, the band name and transmit
+/// frequency when the offset pushes transmit outside that band.
+///
+/// Uses the command's own offset when it carries one, otherwise the VFO's.
+fn offset_tx_outside_band(cmd: &RadioCommand, vfo: &VfoState) -> Option<(&'static str, u64)> {
+    let (dir, offset_hz) = match cmd {
+        RadioCommand::SetDuplexConfig {
+            direction,
+            offset_hz,
+        } => (*direction, *offset_hz),
+        RadioCommand::SetDuplex(dir) => (*dir, vfo.offset?.hz()),
+        _ => return None,
+    };
+    let rx_hz = vfo.frequency?.hz();
+    let band = tx_band(rx_hz)?;
+    let tx_hz = match dir {
+        duplex_sub::PLUS => rx_hz.checked_add(offset_hz)?,
+        duplex_sub::MINUS => rx_hz.checked_sub(offset_hz)?,
+        _ => return None,
+    };
+    (!band.edges.contains(&tx_hz)).then_some((band.name, tx_hz))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn freq(hz: u64) -> Frequency {
+        Frequency::from_hz(hz).unwrap()
+    }
+
+    fn vfo(hz: Option<u64>, mode: Option<OperatingMode>) -> VfoState {
+        VfoState {
+            frequency: hz.map(freq),
+            mode,
+            ..VfoState::default()
+        }
+    }
+
+    fn explain_ng(cmd: &RadioCommand, vfo: &VfoState) -> Option<String> {
+        explain(cmd, vfo, &CivError::Ng)
+    }
+
+    #[test]
+    fn test_frequency_outside_coverage() {
+        let state = vfo(Some(146_520_000), Some(OperatingMode::Fm));
+        let msg = explain_ng(&RadioCommand::SetFrequency(freq(1_200_000_000)), &state).unwrap();
+        assert_eq!(
+            msg,
+            "radio rejected SetFrequency(1200.000 MHz) \u{2014} \
+             1200.000 MHz is outside the radio's receive coverage"
+        );
+        assert!(explain_ng(&RadioCommand::SetFrequency(freq(162_550_000)), &state).is_none());
+    }
+
+    #[test]
+    fn test_dv_outside_coverage() {
+        let msg = explain_ng(
+            &RadioCommand::SetMode(OperatingMode::Dv),
+            &vfo(Some(118_000_000), Some(OperatingMode::Am)),
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            "radio rejected SetMode(DV) \u{2014} \
+             current frequency 118.000 MHz is outside DV coverage (2 m and 70 cm only)"
+        );
+    }
+
+    #[test]
+    fn test_dv_hint_silent_on_ham_bands_and_unknown_frequency() {
+        let dv = RadioCommand::SetMode(OperatingMode::Dv);
+        assert!(explain_ng(&dv, &vfo(Some(145_000_000), None)).is_none());
+        assert!(explain_ng(&dv, &vfo(Some(440_000_000), None)).is_none());
+        assert!(explain_ng(&dv, &vfo(None, None)).is_none());
+        // Other modes on airband are not covered by this entry.
+        let am = RadioCommand::SetMode(OperatingMode::Am);
+        assert!(explain_ng(&am, &vfo(Some(118_000_000), None)).is_none());
+    }
+
+    #[test]
+    fn test_tone_in_dv() {
+        let state = vfo(Some(145_000_000), Some(OperatingMode::Dv));
+        for cmd in [
+            RadioCommand::SetToneMode(0x01),
+            RadioCommand::SetTxTone(1000),
+            RadioCommand::SetRxTone(1000),
+            RadioCommand::SetDtcsCode(0, 0, 23),
+        ] {
+            let msg = explain_ng(&cmd, &state).unwrap();
+            assert!(msg.ends_with("tone and DTCS settings are not available in DV mode"));
+        }
+        let msg = explain_ng(&RadioCommand::SetTxTone(885), &state).unwrap();
+        assert!(msg.starts_with("radio rejected SetTxTone(88.5 Hz)"));
+    }
+
+    #[test]
+    fn test_tone_hint_silent_outside_dv() {
+        let cmd = RadioCommand::SetTxTone(1000);
+        assert!(explain_ng(&cmd, &vfo(Some(145_000_000), Some(OperatingMode::Fm))).is_none());
+        assert!(explain_ng(&cmd, &vfo(Some(145_000_000), None)).is_none());
+    }
+
+    #[test]
+    fn test_duplex_on_receive_only_frequency() {
+        let state = vfo(Some(162_550_000), Some(OperatingMode::Fm));
+        let msg = explain_ng(&RadioCommand::SetDuplex(duplex_sub::PLUS), &state).unwrap();
+        assert_eq!(
+            msg,
+            "radio rejected SetDuplex(DUP+) \u{2014} \
+             current frequency 162.550 MHz is receive-only, so duplex is unavailable"
+        );
+        // Going back to simplex is never explained by this entry.
+        assert!(explain_ng(&RadioCommand::SetDuplex(duplex_sub::SIMPLEX), &state).is_none());
+        assert!(explain_ng(&RadioCommand::SetDuplex(duplex_sub::PLUS), &vfo(None, None)).is_none());
+    }
+
+    #[test]
+    fn test_offset_pushes_tx_out_of_band() {
+        let state = vfo(Some(147_000_000), Some(OperatingMode::Fm));
+        let cmd = RadioCommand::SetDuplexConfig {
+            direction: duplex_sub::PLUS,
+            offset_hz: 5_000_000,
+        };
+        let msg = explain_ng(&cmd, &state).unwrap();
+        assert_eq!(
+            msg,
+            "radio rejected SetDuplex(DUP+) \u{2014} \
+             offset would put the transmit frequency at 152.000 MHz, outside the 2 m band"
+        );
+
+        // The offset write itself is named when that is the step the radio refused.
+        let msg = explain_duplex_step(&cmd, DuplexStep::Offset, &state, &CivError::Ng).unwrap();
+        assert!(
+            msg.starts_with("radio rejected SetOffset(5.000 MHz) for SetDuplex(DUP+) \u{2014} ")
+        );
+        let msg = explain_duplex_step(&cmd, DuplexStep::Direction, &state, &CivError::Ng).unwrap();
+        assert!(msg.starts_with("radio rejected SetDuplex(DUP+) \u{2014} "));
+
+        // A bare direction change uses the VFO's current offset.
+        let state = VfoState {
+            offset: Some(freq(5_000_000)),
+            ..vfo(Some(432_000_000), None)
+        };
+        let msg = explain_ng(&RadioCommand::SetDuplex(duplex_sub::MINUS), &state).unwrap();
+        assert!(msg.ends_with("427.000 MHz, outside the 70 cm band"));
+    }
+
+    #[test]
+    fn test_offset_hint_silent_for_in_band_offsets() {
+        let state = vfo(Some(146_940_000), Some(OperatingMode::Fm));
+        let cmd = RadioCommand::SetDuplexConfig {
+            direction: duplex_sub::MINUS,
+            offset_hz: 600_000,
+        };
+        assert!(explain_ng(&cmd, &state).is_none());
+        // No offset known: nothing to judge.
+        assert!(explain_ng(&RadioCommand::SetDuplex(duplex_sub::MINUS), &state).is_none());
+    }
+
+    #[test]
+    fn test_only_ng_is_explained() {
+        let state = vfo(Some(118_000_000), None);
+        let cmd = RadioCommand::SetMode(OperatingMode::Dv);
+        assert!(explain(&cmd, &state, &CivError::Timeout).is_none());
+        assert!(explain(&cmd, &state, &CivError::Ng).is_some());
+    }
+
+    #[test]
+    fn test_unlisted_commands_keep_plain_message() {
+        let state = vfo(Some(118_000_000), Some(OperatingMode::Dv));
+        assert!(explain_ng(&RadioCommand::SetAfLevel(128), &state).is_none());
+        assert!(explain_ng(&RadioCommand::SelectVfo(crate::message::Vfo::B), &state).is_none());
+    }
+}
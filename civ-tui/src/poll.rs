@@ -0,0 +1,376 @@
+//! Per-field outcomes of the radio poll loop.
+//!
+//! Every poll reads a dozen fields, and a failed read used to collapse into
+//! `None` whatever the cause. A field the radio rejects with NG (not
+//! available in this mode, or at all) looks the same as one whose reply we
+//! failed to parse, which is how a broken offset decoder went unnoticed. The
+//! poll now records each field's outcome in a [`PollReport`]; the radio task
+//! sends it whenever it changes, and the TUI warns once per field as it
+//! starts failing.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use civ_protocol::{CivError, FrameError};
+
+/// Fields read on every poll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum PollField {
+    Frequency,
+    Mode,
+    RfPower,
+    ToneMode,
+    Duplex,
+    Offset,
+    TxTone,
+    RxTone,
+    Dtcs,
+    SMeter,
+    AfLevel,
+    Squelch,
+    GpsPosition,
+}
+
+impl fmt::Display for PollField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Frequency => "frequency",
+            Self::Mode => "mode",
+            Self::RfPower => "RF power",
+            Self::ToneMode => "tone mode",
+            Self::Duplex => "duplex",
+            Self::Offset => "offset",
+            Self::TxTone => "Tx tone",
+            Self::RxTone => "Rx tone",
+            Self::Dtcs => "DTCS",
+            Self::SMeter => "S-meter",
+            Self::AfLevel => "AF level",
+            Self::Squelch => "squelch",
+            Self::GpsPosition => "GPS position",
+        };
+        f.write_str(name)
+    }
+}
+
+/// How a single field read went. The value itself lands in the polled state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReadOutcome {
+    Ok,
+    /// The radio answered NG: not available right now (e.g. tones in DV) or at all.
+    Unsupported,
+    /// Timeout, I/O failure or a reply we could not parse.
+    Error(ReadError),
+}
+
+impl ReadOutcome {
+    pub fn from_error(e: &CivError) -> Self {
+        match e {
+            CivError::Ng => Self::Unsupported,
+            CivError::Timeout => Self::Error(ReadError::Timeout),
+            CivError::InvalidFrame(reason) => Self::Error(ReadError::InvalidFrame(reason.clone())),
+            CivError::Serial(e) => Self::Error(ReadError::Transport(e.to_string())),
+            CivError::Io(e) => Self::Error(ReadError::Transport(e.to_string())),
+            other => Self::Error(ReadError::Decode(other.to_string())),
+        }
+    }
+}
+
+/// Why a field read failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReadError {
+    /// No reply from the radio in time.
+    Timeout,
+    /// The serial link failed.
+    Transport(String),
+    /// The reply was not a well-formed answer to the read.
+    InvalidFrame(FrameError),
+    /// The reply was well-formed but its value could not be decoded.
+    Decode(String),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Timeout => f.write_str("timeout waiting for response"),
+            Self::Transport(reason) | Self::Decode(reason) => f.write_str(reason),
+            Self::InvalidFrame(reason) => write!(f, "invalid CI-V frame: {reason}"),
+        }
+    }
+}
+
+/// Outcome of every field read during one poll.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PollReport {
+    outcomes: BTreeMap<PollField, ReadOutcome>,
+}
+
+impl PollReport {
+    /// Fields that failed with an error, with the error.
+    pub fn errors(&self) -> impl Iterator<Item = (PollField, &ReadError)> {
+        self.outcomes
+            .iter()
+            .filter_map(|(field, outcome)| match outcome {
+                ReadOutcome::Error(reason) => Some((*field, reason)),
+                _ => None,
+            })
+    }
+}
+
+/// Polls between re-reads of a field the radio reported as unsupported.
+const REPROBE_INTERVAL: u64 = 10;
+
+/// Which fields the radio currently answers with NG.
+///
+/// Unsupported fields are only re-read every [`REPROBE_INTERVAL`] polls to
+/// save bus time. Only NG marks a field unsupported: a field that fails with
+/// an error keeps being read every poll so the failure stays visible.
+#[derive(Debug, Default)]
+pub struct Capabilities {
+    unsupported: BTreeSet<PollField>,
+    polls: u64,
+}
+
+impl Capabilities {
+    /// Forget everything learned so far, e.g. after the mode changed.
+    pub fn reset(&mut self) {
+        self.unsupported.clear();
+    }
+
+    pub fn is_supported(&self, field: PollField) -> bool {
+        !self.unsupported.contains(&field)
+    }
+
+    fn should_read(&self, field: PollField) -> bool {
+        self.is_supported(field) || self.polls.is_multiple_of(REPROBE_INTERVAL)
+    }
+
+    fn record(&mut self, field: PollField, outcome: &ReadOutcome) {
+        match outcome {
+            ReadOutcome::Ok => {
+                self.unsupported.remove(&field);
+            }
+            ReadOutcome::Unsupported => {
+                self.unsupported.insert(field);
+            }
+            ReadOutcome::Error(_) => {}
+        }
+    }
+}
+
+/// One pass over the fields, recording outcomes as it goes.
+pub struct Poll<'a> {
+    capabilities: &'a mut Capabilities,
+    report: PollReport,
+}
+
+impl<'a> Poll<'a> {
+    pub fn new(capabilities: &'a mut Capabilities) -> Self {
+        capabilities.polls += 1;
+        Self {
+            capabilities,
+            report: PollReport::default(),
+        }
+    }
+
+    /// Read `field` with `read` unless it is known to be unsupported.
+    pub fn read<T>(
+        &mut self,
+        field: PollField,
+        read: impl FnOnce() -> civ_protocol::Result<T>,
+    ) -> Option<T> {
+        if !self.capabilities.should_read(field) {
+            self.report.outcomes.insert(field, ReadOutcome::Unsupported);
+            return None;
+        }
+        let (value, outcome) = match read() {
+            Ok(value) => (Some(value), ReadOutcome::Ok),
+            Err(e) => (None, ReadOutcome::from_error(&e)),
+        };
+        self.capabilities.record(field, &outcome);
+        self.report.outcomes.insert(field, outcome);
+        value
+    }
+
+    pub fn finish(self) -> PollReport {
+        self.report
+    }
+}
+
+/// Turns a stream of reports into one warning per field as it starts failing.
+#[derive(Debug, Default)]
+pub struct PollWarnings {
+    failing: BTreeSet<PollField>,
+}
+
+impl PollWarnings {
+    /// Return warnings for fields that failed in `report` but not in the
+    /// previous one. A field that recovers warns again if it fails later.
+    pub fn update(&mut self, report: &PollReport) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut failing = BTreeSet::new();
+        for (field, reason) in report.errors() {
+            if !self.failing.contains(&field) {
+                warnings.push(format!("reading {field} failed: {reason}"));
+            }
+            failing.insert(field);
+        }
+        self.failing = failing;
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHORT_OFFSET: FrameError = FrameError::Length {
+        expected: 3,
+        actual: 2,
+    };
+
+    /// Run one poll reading only `field` with `result`, counting reads.
+    fn poll_once(
+        caps: &mut Capabilities,
+        field: PollField,
+        result: civ_protocol::Result<u16>,
+        reads: &mut u32,
+    ) -> (Option<u16>, PollReport) {
+        let mut poll = Poll::new(caps);
+        let value = poll.read(field, || {
+            *reads += 1;
+            result
+        });
+        (value, poll.finish())
+    }
+
+    #[test]
+    fn test_outcome_from_error() {
+        assert_eq!(
+            ReadOutcome::from_error(&CivError::Ng),
+            ReadOutcome::Unsupported
+        );
+        assert_eq!(
+            ReadOutcome::from_error(&CivError::InvalidFrame(FrameError::MissingSubCommand)),
+            ReadOutcome::Error(ReadError::InvalidFrame(FrameError::MissingSubCommand))
+        );
+        assert_eq!(
+            ReadOutcome::from_error(&CivError::Timeout),
+            ReadOutcome::Error(ReadError::Timeout)
+        );
+        assert_eq!(
+            ReadOutcome::from_error(&CivError::InvalidBcd(0xAB)),
+            ReadOutcome::Error(ReadError::Decode("invalid BCD data: 0xab".into()))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_warns_once_and_keeps_polling() {
+        let mut caps = Capabilities::default();
+        let mut warnings = PollWarnings::default();
+        let mut reads = 0;
+        let mut logged = Vec::new();
+
+        let (value, report) = poll_once(&mut caps, PollField::Offset, Ok(600), &mut reads);
+        assert_eq!(value, Some(600));
+        logged.extend(warnings.update(&report));
+
+        for _ in 0..5 {
+            let (value, report) = poll_once(
+                &mut caps,
+                PollField::Offset,
+                Err(CivError::InvalidFrame(SHORT_OFFSET)),
+                &mut reads,
+            );
+            assert_eq!(value, None);
+            assert_eq!(
+                report.outcomes.get(&PollField::Offset),
+                Some(&ReadOutcome::Error(ReadError::InvalidFrame(SHORT_OFFSET)))
+            );
+            logged.extend(warnings.update(&report));
+        }
+
+        assert_eq!(
+            logged,
+            ["reading offset failed: invalid CI-V frame: expected 3 data byte(s), got 2"]
+        );
+        assert_eq!(reads, 6, "a failing field is still read every poll");
+        assert!(caps.is_supported(PollField::Offset));
+    }
+
+    #[test]
+    fn test_recovered_field_warns_again() {
+        let mut caps = Capabilities::default();
+        let mut warnings = PollWarnings::default();
+        let mut reads = 0;
+        let mut count = |result| {
+            let (_, report) = poll_once(&mut caps, PollField::Mode, result, &mut reads);
+            warnings.update(&report).len()
+        };
+        assert_eq!(count(Err(CivError::Timeout)), 1);
+        assert_eq!(count(Err(CivError::Timeout)), 0);
+        assert_eq!(count(Ok(1)), 0);
+        assert_eq!(count(Err(CivError::InvalidFrame(SHORT_OFFSET))), 1);
+    }
+
+    #[test]
+    fn test_unsupported_is_skipped_until_reprobe() {
+        let mut caps = Capabilities::default();
+        let mut warnings = PollWarnings::default();
+        let mut reads = 0;
+
+        let (_, report) = poll_once(&mut caps, PollField::TxTone, Err(CivError::Ng), &mut reads);
+        assert_eq!(
+            report.outcomes.get(&PollField::TxTone),
+            Some(&ReadOutcome::Unsupported)
+        );
+        assert!(!caps.is_supported(PollField::TxTone));
+        assert!(warnings.update(&report).is_empty(), "NG is not a failure");
+
+        // Skipped polls still report the field as unsupported.
+        for _ in 1..REPROBE_INTERVAL - 1 {
+            let (_, report) = poll_once(&mut caps, PollField::TxTone, Ok(885), &mut reads);
+            assert_eq!(
+                report.outcomes.get(&PollField::TxTone),
+                Some(&ReadOutcome::Unsupported)
+            );
+        }
+        assert_eq!(reads, 1);
+
+        // The reprobe succeeds and the field is polled normally again.
+        let (value, _) = poll_once(&mut caps, PollField::TxTone, Ok(885), &mut reads);
+        assert_eq!((value, reads), (Some(885), 2));
+        assert!(caps.is_supported(PollField::TxTone));
+    }
+
+    #[test]
+    fn test_reset_reprobes_immediately() {
+        let mut caps = Capabilities::default();
+        let mut reads = 0;
+        poll_once(&mut caps, PollField::Dtcs, Err(CivError::Ng), &mut reads);
+        caps.reset();
+        let (value, _) = poll_once(&mut caps, PollField::Dtcs, Ok(23), &mut reads);
+        assert_eq!((value, reads), (Some(23), 2));
+    }
+
+    #[test]
+    fn test_report_serde_round_trip() {
+        let mut caps = Capabilities::default();
+        let mut poll = Poll::new(&mut caps);
+        poll.read(PollField::Frequency, || Ok(()));
+        poll.read(PollField::Offset, || {
+            Err::<(), _>(CivError::InvalidFrame(SHORT_OFFSET))
+        });
+        poll.read(PollField::TxTone, || Err::<(), _>(CivError::Ng));
+        poll.read(PollField::Mode, || {
+            Err::<(), _>(CivError::InvalidFrame(FrameError::UnexpectedResponse(
+                "ReadMode",
+            )))
+        });
+        let report = poll.finish();
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(serde_json::from_str::<PollReport>(&json).unwrap(), report);
+    }
+}
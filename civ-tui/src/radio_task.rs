@@ -4,9 +4,18 @@ use std::time::{Duration, Instant};
 
 use tokio::sync::mpsc as tokio_mpsc;
 
-use civ_protocol::Radio;
+use civ_protocol::band::tx_band;
+use civ_protocol::command::duplex_sub;
+use civ_protocol::gps::GpsTracker;
+use civ_protocol::{CivError, Frequency, Radio};
 
-use crate::message::{GpsPosition, RadioCommand, RadioEvent, RadioState, Vfo, VfoState};
+use crate::command_filter::CommandFilter;
+use crate::explorer::{RawOutcome, RawRequest, RawResult, RawTarget};
+use crate::message::{
+    CommandResult, GpsPosition, RadioCommand, RadioEvent, RadioRequest, RadioState, Vfo, VfoState,
+};
+use crate::ng_hint::{self, DuplexStep};
+use crate::poll::{Capabilities, Poll, PollField, PollReport};
 
 /// Bits per byte on the wire with 8N1 framing (1 start + 8 data + 1 stop).
 const BITS_PER_BYTE: u64 = 10;
@@ -14,11 +23,13 @@ const BITS_PER_BYTE: u64 = 10;
 /// Run the radio polling loop on a blocking thread.
 ///
 /// Reads radio state every ~200ms and sends updates via `event_tx`.
-/// Executes commands received on `cmd_rx` immediately.
+/// Executes commands received on `cmd_rx` immediately, except Set commands
+/// that `filter` finds would not change anything.
 pub fn radio_loop(
     mut radio: Radio,
-    cmd_rx: std_mpsc::Receiver<RadioCommand>,
+    cmd_rx: std_mpsc::Receiver<RadioRequest>,
     event_tx: tokio_mpsc::UnboundedSender<RadioEvent>,
+    mut filter: CommandFilter,
 ) {
     let _ = event_tx.send(RadioEvent::Connected);
 
@@ -38,17 +49,34 @@ pub fn radio_loop(
     // Allow the radio time to boot before polling.
     thread::sleep(Duration::from_millis(500));
 
+    // Fields the radio answers with NG; forgotten whenever a command may
+    // have changed what is available (mode, VFO, ...).
+    let mut capabilities = Capabilities::default();
+
     // Initialization: read both VFOs on startup.
     // Start by selecting VFO A and reading its state.
     let _ = radio.select_vfo_a();
-    let (mut cached_vfo_a, s_meter, af_level, squelch, gps_position) = poll_state(&mut radio);
+    let (mut cached_vfo_a, s_meter, af_level, squelch, gps_position, report) =
+        poll_state(&mut radio, &mut capabilities);
 
     // Switch to VFO B and read its state.
     let _ = radio.select_vfo_b();
-    let (mut cached_vfo_b, _, _, _, _) = poll_state(&mut radio);
+    capabilities.reset();
+    let (mut cached_vfo_b, _, _, _, _, _) = poll_state(&mut radio, &mut capabilities);
 
     // Switch back to VFO A (the default active VFO).
     let _ = radio.select_vfo_a();
+    capabilities.reset();
+
+    filter.polled(&cached_vfo_a, af_level, squelch);
+
+    // Per-field read outcomes, sent whenever they change.
+    let _ = event_tx.send(RadioEvent::PollReport(report.clone()));
+    let mut last_report = report;
+
+    // Turns GPS polls into movement and fix notifications.
+    let mut gps_tracker = GpsTracker::default();
+    report_gps_events(&mut gps_tracker, gps_position.as_ref(), &event_tx);
 
     // Send the initial state to the TUI.
     let _ = event_tx.send(RadioEvent::StateUpdate(RadioState {
@@ -63,21 +91,57 @@ pub fn radio_loop(
     }));
 
     loop {
+        let active_state = match active_vfo {
+            Vfo::A => &cached_vfo_a,
+            Vfo::B => &cached_vfo_b,
+        };
+
         // Process any pending commands (non-blocking).
         match cmd_rx.try_recv() {
-            Ok(RadioCommand::Quit) => {
+            Ok(RadioRequest::Quit) => {
                 let _ = event_tx.send(RadioEvent::Disconnected);
                 return;
             }
-            Ok(RadioCommand::SelectVfo(vfo)) => {
+            Ok(RadioRequest::ReadGpsMessage) => {
+                let event = match radio.read_gps_message() {
+                    Ok(text) => RadioEvent::GpsMessage(text),
+                    Err(e) => RadioEvent::Error(format!("GPS message: {e}")),
+                };
+                let _ = event_tx.send(event);
+            }
+            Ok(RadioRequest::Command(RadioCommand::SelectVfo(vfo))) => {
                 active_vfo = vfo;
-                if let Err(e) = execute_command(&mut radio, &RadioCommand::SelectVfo(vfo)) {
-                    let _ = event_tx.send(RadioEvent::Error(format!("{e}")));
+                capabilities.reset();
+                let cmd = RadioCommand::SelectVfo(vfo);
+                let result = execute_command(&mut radio, &cmd, active_state, &event_tx);
+                filter.record(&cmd, result.is_ok());
+                if let Err(msg) = result {
+                    let _ = event_tx.send(RadioEvent::Error(msg));
+                }
+            }
+            Ok(RadioRequest::Raw(request)) => {
+                if let RawRequest::Write(..) = request {
+                    // A raw write may touch any of the polled fields.
+                    capabilities.reset();
+                    filter.forget();
                 }
+                let _ = event_tx.send(RadioEvent::Raw(raw_access(&mut radio, &request)));
+            }
+            Ok(RadioRequest::Command(cmd)) if filter.is_noop(&cmd) => {
+                let _ = event_tx.send(RadioEvent::CommandResult(CommandResult {
+                    command: cmd.label(),
+                    skipped: true,
+                }));
             }
-            Ok(cmd) => {
-                if let Err(e) = execute_command(&mut radio, &cmd) {
-                    let _ = event_tx.send(RadioEvent::Error(format!("{e}")));
+            Ok(RadioRequest::Command(cmd)) => {
+                capabilities.reset();
+                let result = execute_command(&mut radio, &cmd, active_state, &event_tx);
+                filter.record(&cmd, result.is_ok());
+                match result {
+                    Ok(()) => send_command_result(&cmd, &event_tx),
+                    Err(msg) => {
+                        let _ = event_tx.send(RadioEvent::Error(msg));
+                    }
                 }
             }
             Err(std_mpsc::TryRecvError::Empty) => {}
@@ -85,7 +149,17 @@ pub fn radio_loop(
         }
 
         // Poll radio state for the active VFO.
-        let (vfo_state, s_meter, af_level, squelch, gps_position) = poll_state(&mut radio);
+        let (vfo_state, s_meter, af_level, squelch, gps_position, report) =
+            poll_state(&mut radio, &mut capabilities);
+
+        filter.polled(&vfo_state, af_level, squelch);
+
+        if report != last_report {
+            let _ = event_tx.send(RadioEvent::PollReport(report.clone()));
+            last_report = report;
+        }
+
+        report_gps_events(&mut gps_tracker, gps_position.as_ref(), &event_tx);
 
         // Update the active VFO's cache.
         match active_vfo {
@@ -124,8 +198,65 @@ pub fn radio_loop(
     }
 }
 
-fn execute_command(radio: &mut Radio, cmd: &RadioCommand) -> civ_protocol::Result<()> {
-    match cmd {
+/// Perform a raw explorer read or write.
+///
+/// Failures are part of the result rather than an error event, so they show
+/// up in the explorer's history next to the target that failed.
+fn raw_access(radio: &mut Radio, request: &RawRequest) -> RawResult {
+    let (target, result) = match request {
+        RawRequest::Read(target @ RawTarget::Various(sub)) => {
+            (*target, radio.read_various_raw(*sub).map(RawOutcome::Read))
+        }
+        RawRequest::Read(target @ RawTarget::Setting(item)) => {
+            (*target, radio.read_setting(*item).map(RawOutcome::Read))
+        }
+        RawRequest::Write(target @ RawTarget::Various(sub), value) => (
+            *target,
+            radio
+                .set_various_raw(*sub, value)
+                .map(|()| RawOutcome::Written(value.clone())),
+        ),
+        RawRequest::Write(target @ RawTarget::Setting(item), value) => (
+            *target,
+            radio
+                .set_setting(*item, value)
+                .map(|()| RawOutcome::Written(value.clone())),
+        ),
+    };
+    let outcome = result.unwrap_or_else(|e| RawOutcome::Failed(e.to_string()));
+    RawResult { target, outcome }
+}
+
+/// Tell the TUI that `cmd` went through.
+fn send_command_result(cmd: &RadioCommand, event_tx: &tokio_mpsc::UnboundedSender<RadioEvent>) {
+    let _ = event_tx.send(RadioEvent::CommandResult(CommandResult {
+        command: cmd.label(),
+        skipped: false,
+    }));
+}
+
+/// Feed the latest GPS poll to the tracker and log whatever it reports.
+fn report_gps_events(
+    tracker: &mut GpsTracker,
+    position: Option<&GpsPosition>,
+    event_tx: &tokio_mpsc::UnboundedSender<RadioEvent>,
+) {
+    for event in tracker.update(Instant::now(), position) {
+        let _ = event_tx.send(RadioEvent::Info(format!("GPS {event}")));
+    }
+}
+
+/// Send `cmd` to the radio.
+///
+/// On failure, returns the message for the error log, explained by an NG
+/// hint when one matches `active_state`, the active VFO's last poll.
+fn execute_command(
+    radio: &mut Radio,
+    cmd: &RadioCommand,
+    active_state: &VfoState,
+    event_tx: &tokio_mpsc::UnboundedSender<RadioEvent>,
+) -> Result<(), String> {
+    let result = match cmd {
         RadioCommand::SetFrequency(freq) => radio.set_frequency(*freq),
         RadioCommand::SetMode(mode) => radio.set_mode(*mode),
         RadioCommand::SetAfLevel(level) => radio.set_af_level(*level),
@@ -136,40 +267,120 @@ fn execute_command(radio: &mut Radio, cmd: &RadioCommand) -> civ_protocol::Resul
         },
         RadioCommand::SetRfPower(level) => radio.set_rf_power(*level),
         RadioCommand::SetDuplex(dir) => radio.set_duplex(*dir),
-        RadioCommand::SetOffset(hz) => radio.set_offset(*hz),
+        RadioCommand::SetDuplexConfig {
+            direction,
+            offset_hz,
+        } => {
+            if let Some(warning) =
+                duplex_offset_warning(active_state.frequency, *direction, *offset_hz)
+            {
+                let _ = event_tx.send(RadioEvent::Warning(warning));
+            }
+            // The hint names whichever of the two writes the radio refused.
+            return set_duplex_config(radio, *direction, *offset_hz, event_tx).map_err(
+                |(step, e)| {
+                    ng_hint::explain_duplex_step(cmd, step, active_state, &e)
+                        .unwrap_or_else(|| format!("duplex {}: {e}", step.name()))
+                },
+            );
+        }
         RadioCommand::SetToneMode(mode) => radio.set_tone_mode(*mode),
         RadioCommand::SetTxTone(freq) => radio.set_tx_tone(*freq),
         RadioCommand::SetRxTone(freq) => radio.set_rx_tone(*freq),
         RadioCommand::SetDtcsCode(tx_pol, rx_pol, code) => radio.set_dtcs(*tx_pol, *rx_pol, *code),
+        RadioCommand::SetGpsMessage(text) => radio.set_gps_message(text),
         RadioCommand::PowerOn => radio.power_on(),
         RadioCommand::PowerOff => radio.power_off(),
-        RadioCommand::Quit => Ok(()),
+    };
+    result.map_err(|e| ng_hint::explain(cmd, active_state, &e).unwrap_or_else(|| e.to_string()))
+}
+
+/// Apply a duplex direction and offset as one operation.
+///
+/// The offset is written before the direction. If the radio rejects the offset,
+/// the direction is rolled back to simplex so the radio is never left in
+/// DUP+/DUP- with an offset it didn't accept. The error names the write
+/// that failed.
+fn set_duplex_config(
+    radio: &mut Radio,
+    direction: u8,
+    offset_hz: u64,
+    event_tx: &tokio_mpsc::UnboundedSender<RadioEvent>,
+) -> Result<(), (DuplexStep, CivError)> {
+    if direction != duplex_sub::SIMPLEX
+        && let Err(e) = radio.set_offset(offset_hz)
+    {
+        match radio.set_duplex(duplex_sub::SIMPLEX) {
+            Ok(()) => {
+                let _ = event_tx.send(RadioEvent::Info(
+                    "offset rejected, duplex reset to simplex".to_string(),
+                ));
+            }
+            Err(rollback) => {
+                let _ = event_tx.send(RadioEvent::Error(format!(
+                    "duplex rollback to simplex failed: {rollback}"
+                )));
+            }
+        }
+        return Err((DuplexStep::Offset, e));
     }
+    radio
+        .set_duplex(direction)
+        .map_err(|e| (DuplexStep::Direction, e))
+}
+
+/// Check a duplex offset against the usual offsets for the band in use.
+///
+/// Returns a warning message when the offset is outside the band's sensible
+/// range. Simplex, unknown frequencies and bands without a convention pass.
+fn duplex_offset_warning(
+    frequency: Option<Frequency>,
+    direction: u8,
+    offset_hz: u64,
+) -> Option<String> {
+    if direction == duplex_sub::SIMPLEX {
+        return None;
+    }
+    let band = tx_band(frequency?.hz())?;
+    let range = &band.repeater_offsets;
+    if range.contains(&offset_hz) {
+        return None;
+    }
+    Some(format!(
+        "offset {:.3} MHz is unusual for the {} band (expected {:.3}\u{2013}{:.3} MHz)",
+        band.name,
+        offset_hz as f64 / 1_000_000.0,
+        *range.start() as f64 / 1_000_000.0,
+        *range.end() as f64 / 1_000_000.0,
+    ))
 }
 
 fn poll_state(
     radio: &mut Radio,
+    capabilities: &mut Capabilities,
 ) -> (
     VfoState,
     Option<u16>,
     Option<u16>,
     Option<u16>,
     Option<GpsPosition>,
+    PollReport,
 ) {
-    let frequency = radio.read_frequency().ok();
-    let mode = radio.read_mode().ok();
-    let rf_power = radio.read_rf_power().ok();
-    let tone_mode = radio.read_tone_mode().ok();
-    let duplex = radio.read_duplex().ok();
-    let offset = radio.read_offset().ok();
-    let tx_tone_freq = radio.read_tx_tone().ok();
-    let rx_tone_freq = radio.read_rx_tone().ok();
-    let dtcs = radio.read_dtcs().ok();
-
-    let s_meter = radio.read_s_meter().ok();
-    let af_level = radio.read_af_level().ok();
-    let squelch = radio.read_squelch().ok();
-    let gps_position = radio.read_gps_position().ok();
+    let mut poll = Poll::new(capabilities);
+    let frequency = poll.read(PollField::Frequency, || radio.read_frequency());
+    let mode = poll.read(PollField::Mode, || radio.read_mode());
+    let rf_power = poll.read(PollField::RfPower, || radio.read_rf_power());
+    let tone_mode = poll.read(PollField::ToneMode, || radio.read_tone_mode());
+    let duplex = poll.read(PollField::Duplex, || radio.read_duplex());
+    let offset = poll.read(PollField::Offset, || radio.read_offset());
+    let tx_tone_freq = poll.read(PollField::TxTone, || radio.read_tx_tone());
+    let rx_tone_freq = poll.read(PollField::RxTone, || radio.read_rx_tone());
+    let dtcs = poll.read(PollField::Dtcs, || radio.read_dtcs());
+
+    let s_meter = poll.read(PollField::SMeter, || radio.read_s_meter());
+    let af_level = poll.read(PollField::AfLevel, || radio.read_af_level());
+    let squelch = poll.read(PollField::Squelch, || radio.read_squelch());
+    let gps_position = poll.read(PollField::GpsPosition, || radio.read_gps_position());
 
     let vfo_state = VfoState {
         frequency,
@@ -185,5 +396,342 @@ fn poll_state(
         offset,
     };
 
-    (vfo_state, s_meter, af_level, squelch, gps_position)
+    (
+        vfo_state,
+        s_meter,
+        af_level,
+        squelch,
+        gps_position,
+        poll.finish(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::io;
+    use std::sync::{Arc, Mutex};
+
+    use civ_protocol::RadioConfig;
+    use civ_protocol::command::{Command, cmd};
+    use civ_protocol::protocol::{ADDR_CONTROLLER, ADDR_ID52, EOM, NG, OK, PREAMBLE};
+    use civ_protocol::transport::Transport;
+
+    use super::*;
+    use crate::poll::PollWarnings;
+
+    /// Transport that records every frame written and answers each one with
+    /// the next scripted reply.
+    struct MockTransport {
+        written: Arc<Mutex<Vec<Vec<u8>>>>,
+        replies: VecDeque<Vec<u8>>,
+        pending: Vec<u8>,
+    }
+
+    impl Transport for MockTransport {
+        fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+            self.written.lock().unwrap().push(buf.to_vec());
+            if let Some(reply) = self.replies.pop_front() {
+                self.pending.extend_from_slice(&reply);
+            }
+            Ok(())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.pending.is_empty() {
+                return Err(io::ErrorKind::TimedOut.into());
+            }
+            let n = buf.len().min(self.pending.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            Ok(n)
+        }
+
+        fn set_read_timeout(&mut self, _timeout: Duration) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn reply(command: u8) -> Vec<u8> {
+        vec![PREAMBLE, PREAMBLE, ADDR_CONTROLLER, ADDR_ID52, command, EOM]
+    }
+
+    fn mock_radio(replies: Vec<Vec<u8>>) -> (Radio, Arc<Mutex<Vec<Vec<u8>>>>) {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let transport = MockTransport {
+            written: Arc::clone(&written),
+            replies: replies.into(),
+            pending: Vec::new(),
+        };
+        let config = RadioConfig {
+            timeout: Duration::from_millis(50),
+            ..RadioConfig::default()
+        };
+        (Radio::new(Box::new(transport), config), written)
+    }
+
+    fn frame(command: Command) -> Vec<u8> {
+        command.to_frame().unwrap().to_bytes()
+    }
+
+    fn mhz(hz: u64) -> Option<Frequency> {
+        Some(Frequency::from_hz(hz).unwrap())
+    }
+
+    fn offset_reply(data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![
+            PREAMBLE,
+            PREAMBLE,
+            ADDR_CONTROLLER,
+            ADDR_ID52,
+            cmd::READ_OFFSET,
+        ];
+        bytes.extend_from_slice(data);
+        bytes.push(EOM);
+        bytes
+    }
+
+    #[test]
+    fn test_poll_state_warns_once_when_a_field_starts_failing() {
+        const GOOD: [u8; 3] = [0x00, 0x60, 0x00];
+        const SHORT: [u8; 2] = [0x00, 0x60];
+        // First poll reads every field; all but the offset answer NG, so
+        // later polls (before the reprobe) read only the offset.
+        let mut replies = vec![reply(NG); 5];
+        replies.push(offset_reply(&GOOD));
+        replies.extend(vec![reply(NG); 7]);
+        replies.extend([
+            offset_reply(&SHORT),
+            offset_reply(&SHORT),
+            offset_reply(&GOOD),
+        ]);
+        let (mut radio, written) = mock_radio(replies);
+        let mut capabilities = Capabilities::default();
+        let mut warnings = PollWarnings::default();
+        let mut offsets = Vec::new();
+        let mut logged = Vec::new();
+
+        for _ in 0..4 {
+            let (state, .., report) = poll_state(&mut radio, &mut capabilities);
+            offsets.push(state.offset.map(|f| f.hz()));
+            logged.extend(warnings.update(&report));
+        }
+
+        assert_eq!(offsets, [Some(600_000), None, None, Some(600_000)]);
+        assert_eq!(
+            logged,
+            ["reading offset failed: invalid CI-V frame: expected 3 data byte(s), got 2"]
+        );
+        // The failing offset was read on every poll.
+        assert_eq!(written.lock().unwrap().len(), 13 + 3);
+    }
+
+    #[test]
+    fn test_duplex_config_writes_offset_before_direction() {
+        let (mut radio, written) = mock_radio(vec![reply(OK), reply(OK)]);
+        let (event_tx, mut event_rx) = tokio_mpsc::unbounded_channel();
+
+        set_duplex_config(&mut radio, duplex_sub::PLUS, 600_000, &event_tx).unwrap();
+
+        assert_eq!(
+            *written.lock().unwrap(),
+            vec![
+                frame(Command::SetOffset(600_000)),
+                frame(Command::SetDuplex(duplex_sub::PLUS))
+            ]
+        );
+        assert!(event_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_duplex_config_simplex_skips_offset() {
+        let (mut radio, written) = mock_radio(vec![reply(OK)]);
+        let (event_tx, _event_rx) = tokio_mpsc::unbounded_channel();
+
+        set_duplex_config(&mut radio, duplex_sub::SIMPLEX, 600_000, &event_tx).unwrap();
+
+        assert_eq!(
+            *written.lock().unwrap(),
+            vec![frame(Command::SetDuplex(duplex_sub::SIMPLEX))]
+        );
+    }
+
+    #[test]
+    fn test_duplex_config_rejected_offset_rolls_back_to_simplex() {
+        let (mut radio, written) = mock_radio(vec![reply(NG), reply(OK)]);
+        let (event_tx, mut event_rx) = tokio_mpsc::unbounded_channel();
+
+        let result = set_duplex_config(&mut radio, duplex_sub::PLUS, 600_000, &event_tx);
+
+        assert!(matches!(result, Err((DuplexStep::Offset, CivError::Ng))));
+        // The requested direction is never written; simplex is restored instead.
+        assert_eq!(
+            *written.lock().unwrap(),
+            vec![
+                frame(Command::SetOffset(600_000)),
+                frame(Command::SetDuplex(duplex_sub::SIMPLEX))
+            ]
+        );
+        assert!(matches!(event_rx.try_recv(), Ok(RadioEvent::Info(_))));
+    }
+
+    #[test]
+    fn test_execute_duplex_config_warns_and_names_the_rejected_write() {
+        let (mut radio, _written) = mock_radio(vec![reply(NG), reply(OK)]);
+        let (event_tx, mut event_rx) = tokio_mpsc::unbounded_channel();
+        let active_state = VfoState {
+            frequency: mhz(146_520_000),
+            ..VfoState::default()
+        };
+        let cmd = RadioCommand::SetDuplexConfig {
+            direction: duplex_sub::PLUS,
+            offset_hz: 5_000_000,
+        };
+
+        let result = execute_command(&mut radio, &cmd, &active_state, &event_tx);
+
+        assert_eq!(
+            result,
+            Err(
+                "radio rejected SetOffset(5.000 MHz) for SetDuplex(DUP+) \u{2014} \
+                 offset would put the transmit frequency at 151.520 MHz, outside the 2 m band"
+                    .to_string()
+            )
+        );
+        assert!(matches!(event_rx.try_recv(), Ok(RadioEvent::Warning(_))));
+        assert!(matches!(event_rx.try_recv(), Ok(RadioEvent::Info(_))));
+    }
+
+    #[test]
+    fn test_raw_setting_read_reports_value_bytes() {
+        let response = vec![
+            PREAMBLE,
+            PREAMBLE,
+            ADDR_CONTROLLER,
+            ADDR_ID52,
+            0x1A,
+            0x05,
+            0x01,
+            0x23,
+            0x00,
+            0x42,
+            EOM,
+        ];
+        let (mut radio, written) = mock_radio(vec![response]);
+
+        let result = raw_access(&mut radio, &RawRequest::Read(RawTarget::Setting(123)));
+
+        assert_eq!(
+            *written.lock().unwrap(),
+            vec![frame(Command::ReadSetting(123))]
+        );
+        assert_eq!(
+            result,
+            RawResult {
+                target: RawTarget::Setting(123),
+                outcome: RawOutcome::Read(vec![0x00, 0x42]),
+            }
+        );
+    }
+
+    #[test]
+    fn test_raw_various_read_reports_every_value_byte() {
+        let response = vec![
+            PREAMBLE,
+            PREAMBLE,
+            ADDR_CONTROLLER,
+            ADDR_ID52,
+            cmd::VARIOUS,
+            0x02,
+            0x01,
+            0x23,
+            EOM,
+        ];
+        let (mut radio, written) = mock_radio(vec![response]);
+
+        let result = raw_access(&mut radio, &RawRequest::Read(RawTarget::Various(0x02)));
+
+        assert_eq!(
+            *written.lock().unwrap(),
+            vec![frame(Command::ReadVariousRaw(0x02))]
+        );
+        assert_eq!(result.outcome, RawOutcome::Read(vec![0x01, 0x23]));
+    }
+
+    #[test]
+    fn test_raw_writes_report_written_bytes_or_failure() {
+        let (mut radio, written) = mock_radio(vec![reply(OK), reply(NG)]);
+
+        let ok = raw_access(
+            &mut radio,
+            &RawRequest::Write(RawTarget::Various(0x5D), vec![0x00, 0x01]),
+        );
+        let ng = raw_access(
+            &mut radio,
+            &RawRequest::Write(RawTarget::Setting(7), vec![0x01, 0x02]),
+        );
+
+        assert_eq!(
+            *written.lock().unwrap(),
+            vec![
+                frame(Command::SetVariousRaw(0x5D, vec![0x00, 0x01])),
+                frame(Command::SetSetting(7, vec![0x01, 0x02]))
+            ]
+        );
+        assert_eq!(ok.outcome, RawOutcome::Written(vec![0x00, 0x01]));
+        assert_eq!(ng.target, RawTarget::Setting(7));
+        assert_eq!(
+            ng.outcome,
+            RawOutcome::Failed("radio returned NG (command rejected)".into())
+        );
+    }
+
+    #[test]
+    fn test_raw_result_event_round_trips() {
+        // Raw results are recorded with the rest of the session.
+        let event = RadioEvent::Raw(RawResult {
+            target: RawTarget::Various(0x5D),
+            outcome: RawOutcome::Read(vec![0x09]),
+        });
+        let json = serde_json::to_string(&event).unwrap();
+        let RadioEvent::Raw(back) = serde_json::from_str(&json).unwrap() else {
+            panic!("wrong event: {json}");
+        };
+        assert_eq!(back.outcome, RawOutcome::Read(vec![0x09]));
+    }
+
+    #[test]
+    fn test_duplex_offset_warning_on_wrong_band() {
+        let warning = duplex_offset_warning(mhz(146_520_000), duplex_sub::PLUS, 5_000_000).unwrap();
+        assert!(warning.contains("2 m"), "{warning}");
+    }
+
+    #[test]
+    fn test_duplex_offset_warning_silent_for_sensible_offsets() {
+        assert_eq!(
+            duplex_offset_warning(mhz(146_940_000), duplex_sub::MINUS, 600_000),
+            None
+        );
+        assert_eq!(
+            duplex_offset_warning(mhz(442_000_000), duplex_sub::PLUS, 5_000_000),
+            None
+        );
+        // Simplex ignores the offset, unknown bands and frequencies have no convention.
+        assert_eq!(
+            duplex_offset_warning(mhz(146_520_000), duplex_sub::SIMPLEX, 5_000_000),
+            None
+        );
+        assert_eq!(
+            duplex_offset_warning(mhz(118_000_000), duplex_sub::PLUS, 5_000_000),
+            None
+        );
+        assert_eq!(
+            duplex_offset_warning(None, duplex_sub::PLUS, 5_000_000),
+            None
+        );
+    }
 }
@@ -0,0 +1,387 @@
+//! Recording and replay of TUI sessions for debugging rendering issues.
+//!
+//! `--ui-record <file>` writes every [`AppEvent`] as one JSON object per line,
+//! stamped with milliseconds since recording started. Runs of ticks collapse
+//! into a single entry. `--ui-replay <file>` feeds the entries back through
+//! [`App::handle_event`] and [`ui::draw`], either to the real terminal at the
+//! recorded pace or to a [`TestBackend`] with `--headless`.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc as std_mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::Terminal;
+use ratatui::backend::{Backend, TestBackend};
+use ratatui::buffer::Buffer;
+use serde::{Deserialize, Serialize};
+
+use crate::app::App;
+use crate::event::AppEvent;
+use crate::message::RadioEvent;
+use crate::ui;
+
+/// Terminal size for headless replay of a recording without a `Start` entry.
+const DEFAULT_SIZE: (u16, u16) = (100, 40);
+
+/// One line of a recording.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Entry {
+    /// Milliseconds since the recording started.
+    pub at_ms: u64,
+    pub event: RecordedEvent,
+}
+
+/// Serializable form of an [`AppEvent`].
+#[derive(Debug, Serialize, Deserialize)]
+pub enum RecordedEvent {
+    /// Written once at the top of a recording.
+    Start {
+        baud_rate: u32,
+        size: (u16, u16),
+    },
+    Key(RecordedKey),
+    Radio(RadioEvent),
+    /// A run of consecutive ticks (they only trigger a redraw).
+    Ticks(u32),
+    Resize(u16, u16),
+}
+
+/// Serializable mirror of crossterm's [`KeyEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedKey {
+    pub code: RecordedKeyCode,
+    /// [`KeyModifiers`] bits.
+    #[serde(default)]
+    pub modifiers: u8,
+    #[serde(default)]
+    pub kind: RecordedKeyKind,
+}
+
+/// Mirror of [`KeyCode`], limited to the keys the TUI can act on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordedKeyCode {
+    Char(char),
+    F(u8),
+    Backspace,
+    Enter,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Tab,
+    BackTab,
+    Delete,
+    Insert,
+    Esc,
+    /// Media, modifier and other keys the TUI ignores.
+    Other,
+}
+
+/// Mirror of [`KeyEventKind`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordedKeyKind {
+    #[default]
+    Press,
+    Repeat,
+    Release,
+}
+
+impl From<KeyEvent> for RecordedKey {
+    fn from(key: KeyEvent) -> Self {
+        let code = match key.code {
+            KeyCode::Char(c) => RecordedKeyCode::Char(c),
+            KeyCode::F(n) => RecordedKeyCode::F(n),
+            KeyCode::Backspace => RecordedKeyCode::Backspace,
+            KeyCode::Enter => RecordedKeyCode::Enter,
+            KeyCode::Left => RecordedKeyCode::Left,
+            KeyCode::Right => RecordedKeyCode::Right,
+            KeyCode::Up => RecordedKeyCode::Up,
+            KeyCode::Down => RecordedKeyCode::Down,
+            KeyCode::Home => RecordedKeyCode::Home,
+            KeyCode::End => RecordedKeyCode::End,
+            KeyCode::PageUp => RecordedKeyCode::PageUp,
+            KeyCode::PageDown => RecordedKeyCode::PageDown,
+            KeyCode::Tab => RecordedKeyCode::Tab,
+            KeyCode::BackTab => RecordedKeyCode::BackTab,
+            KeyCode::Delete => RecordedKeyCode::Delete,
+            KeyCode::Insert => RecordedKeyCode::Insert,
+            KeyCode::Esc => RecordedKeyCode::Esc,
+            _ => RecordedKeyCode::Other,
+        };
+        let kind = match key.kind {
+            KeyEventKind::Press => RecordedKeyKind::Press,
+            KeyEventKind::Repeat => RecordedKeyKind::Repeat,
+            KeyEventKind::Release => RecordedKeyKind::Release,
+        };
+        Self {
+            code,
+            modifiers: key.modifiers.bits(),
+            kind,
+        }
+    }
+}
+
+impl From<RecordedKey> for KeyEvent {
+    fn from(key: RecordedKey) -> Self {
+        let code = match key.code {
+            RecordedKeyCode::Char(c) => KeyCode::Char(c),
+            RecordedKeyCode::F(n) => KeyCode::F(n),
+            RecordedKeyCode::Backspace => KeyCode::Backspace,
+            RecordedKeyCode::Enter => KeyCode::Enter,
+            RecordedKeyCode::Left => KeyCode::Left,
+            RecordedKeyCode::Right => KeyCode::Right,
+            RecordedKeyCode::Up => KeyCode::Up,
+            RecordedKeyCode::Down => KeyCode::Down,
+            RecordedKeyCode::Home => KeyCode::Home,
+            RecordedKeyCode::End => KeyCode::End,
+            RecordedKeyCode::PageUp => KeyCode::PageUp,
+            RecordedKeyCode::PageDown => KeyCode::PageDown,
+            RecordedKeyCode::Tab => KeyCode::Tab,
+            RecordedKeyCode::BackTab => KeyCode::BackTab,
+            RecordedKeyCode::Delete => KeyCode::Delete,
+            RecordedKeyCode::Insert => KeyCode::Insert,
+            RecordedKeyCode::Esc => KeyCode::Esc,
+            RecordedKeyCode::Other => KeyCode::Null,
+        };
+        let kind = match key.kind {
+            RecordedKeyKind::Press => KeyEventKind::Press,
+            RecordedKeyKind::Repeat => KeyEventKind::Repeat,
+            RecordedKeyKind::Release => KeyEventKind::Release,
+        };
+        KeyEvent::new_with_kind(code, KeyModifiers::from_bits_truncate(key.modifiers), kind)
+    }
+}
+
+/// Writes a session recording as events arrive.
+pub struct Recorder {
+    out: BufWriter<File>,
+    start: Instant,
+    /// Time of the first tick and number of ticks not yet written.
+    pending_ticks: Option<(u64, u32)>,
+}
+
+impl Recorder {
+    /// Create (or truncate) `path` and write the `Start` entry.
+    pub fn create(path: &Path, baud_rate: u32, size: (u16, u16)) -> io::Result<Self> {
+        let mut recorder = Self {
+            out: BufWriter::new(File::create(path)?),
+            start: Instant::now(),
+            pending_ticks: None,
+        };
+        recorder.write(0, RecordedEvent::Start { baud_rate, size })?;
+        Ok(recorder)
+    }
+
+    /// Record one event.
+    pub fn record(&mut self, event: &AppEvent) -> io::Result<()> {
+        let at_ms = self.start.elapsed().as_millis() as u64;
+        let recorded = match event {
+            AppEvent::Tick => {
+                let (_, count) = self.pending_ticks.get_or_insert((at_ms, 0));
+                *count += 1;
+                return Ok(());
+            }
+            AppEvent::Key(key) => RecordedEvent::Key((*key).into()),
+            AppEvent::Radio(radio_event) => RecordedEvent::Radio(radio_event.clone()),
+            AppEvent::Resize(w, h) => RecordedEvent::Resize(*w, *h),
+        };
+        self.flush_ticks()?;
+        self.write(at_ms, recorded)
+    }
+
+    /// Write any pending ticks and flush the file.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.flush_ticks()?;
+        self.out.flush()
+    }
+
+    fn flush_ticks(&mut self) -> io::Result<()> {
+        match self.pending_ticks.take() {
+            Some((at_ms, count)) => self.write(at_ms, RecordedEvent::Ticks(count)),
+            None => Ok(()),
+        }
+    }
+
+    fn write(&mut self, at_ms: u64, event: RecordedEvent) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, &Entry { at_ms, event })?;
+        self.out.write_all(b"\n")?;
+        // Flush per entry so a crash still leaves a usable recording.
+        self.out.flush()
+    }
+}
+
+/// Load a recording from `path`.
+pub fn load(path: &Path) -> io::Result<Vec<Entry>> {
+    parse(BufReader::new(File::open(path)?))
+}
+
+/// Parse a recording, one JSON entry per line. Blank lines are ignored.
+pub fn parse(reader: impl BufRead) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    for (n, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line).map_err(|e| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("line {}: {e}", n + 1))
+        })?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Baud rate and terminal size from the recording's `Start` entry.
+pub fn start_params(entries: &[Entry]) -> (u32, (u16, u16)) {
+    match entries.first() {
+        Some(Entry {
+            event: RecordedEvent::Start { baud_rate, size },
+            ..
+        }) => (*baud_rate, *size),
+        _ => (0, DEFAULT_SIZE),
+    }
+}
+
+/// Feed `entries` through a fresh [`App`] and draw after each one.
+///
+/// With `paced` the recorded timing is reproduced; otherwise entries are
+/// applied back to back. Either way each event is handled at its recorded
+/// offset, so log timestamps match the recording. Returns the app as it was after the last event.
+pub fn replay<B: Backend>(
+    terminal: &mut Terminal<B>,
+    entries: Vec<Entry>,
+    paced: bool,
+) -> io::Result<App> {
+    let (baud_rate, _) = start_params(&entries);
+    // Commands go nowhere during replay.
+    let (cmd_tx, _cmd_rx) = std_mpsc::channel();
+    let mut app = App::new(cmd_tx, baud_rate);
+    let start = Instant::now();
+
+    terminal.draw(|frame| ui::draw(frame, &app))?;
+    for Entry { at_ms, event } in entries {
+        if paced {
+            let due = start + Duration::from_millis(at_ms);
+            thread::sleep(due.saturating_duration_since(Instant::now()));
+        }
+        let event = match event {
+            RecordedEvent::Start { .. } => continue,
+            RecordedEvent::Key(key) => AppEvent::Key(key.into()),
+            RecordedEvent::Radio(radio_event) => AppEvent::Radio(radio_event),
+            RecordedEvent::Ticks(_) => AppEvent::Tick,
+            RecordedEvent::Resize(w, h) => AppEvent::Resize(w, h),
+        };
+        app.handle_event_at(event, start + Duration::from_millis(at_ms));
+        if app.should_quit {
+            break;
+        }
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+    }
+    Ok(app)
+}
+
+/// Replay into an off-screen [`TestBackend`] and return the final frame.
+pub fn replay_headless(entries: Vec<Entry>) -> io::Result<Buffer> {
+    let (_, (width, height)) = start_params(&entries);
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    // `Resize` entries only matter for real terminals; the off-screen frame
+    // keeps the recorded starting size so snapshots stay comparable.
+    replay(&mut terminal, entries, false)?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Render a buffer as plain text, one line per row, trailing spaces trimmed.
+pub fn buffer_to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let line: String = (area.left()..area.right())
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC_SESSION: &str = include_str!("../tests/data/basic_session.jsonl");
+    const BASIC_SESSION_FRAME: &str = include_str!("../tests/data/basic_session.txt");
+
+    #[test]
+    fn test_key_mirror_roundtrip() {
+        let keys = [
+            KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            KeyEvent::new(KeyCode::F(9), KeyModifiers::SHIFT | KeyModifiers::ALT),
+            KeyEvent::new_with_kind(KeyCode::Enter, KeyModifiers::NONE, KeyEventKind::Release),
+            KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE),
+        ];
+        for key in keys {
+            let json = serde_json::to_string(&RecordedKey::from(key)).unwrap();
+            let back: RecordedKey = serde_json::from_str(&json).unwrap();
+            assert_eq!(KeyEvent::from(back), key, "{json}");
+        }
+    }
+
+    #[test]
+    fn test_recorder_collapses_ticks() {
+        let path = std::env::temp_dir().join(format!("civ-tui-rec-{}.jsonl", std::process::id()));
+        let mut recorder = Recorder::create(&path, 19200, (80, 24)).unwrap();
+        recorder.record(&AppEvent::Tick).unwrap();
+        recorder.record(&AppEvent::Tick).unwrap();
+        recorder.record(&AppEvent::Tick).unwrap();
+        recorder
+            .record(&AppEvent::Radio(RadioEvent::Connected))
+            .unwrap();
+        recorder.record(&AppEvent::Tick).unwrap();
+        recorder.finish().unwrap();
+
+        let entries = load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let events: Vec<_> = entries.iter().map(|e| &e.event).collect();
+        assert!(matches!(
+            events[..],
+            [
+                RecordedEvent::Start {
+                    baud_rate: 19200,
+                    size: (80, 24)
+                },
+                RecordedEvent::Ticks(3),
+                RecordedEvent::Radio(RadioEvent::Connected),
+                RecordedEvent::Ticks(1),
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_parse_reports_line_number() {
+        let err = parse("\n{\"at_ms\":0,\"event\":\"Bogus\"}\n".as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 2:"), "{err}");
+    }
+
+    #[test]
+    fn test_replay_basic_session_snapshot() {
+        let entries = parse(BASIC_SESSION.as_bytes()).unwrap();
+        let buffer = replay_headless(entries).unwrap();
+        let frame = buffer_to_text(&buffer);
+        if frame != BASIC_SESSION_FRAME {
+            panic!(
+                "final frame differs from tests/data/basic_session.txt:\n{frame}\n\
+                 (regenerate with `civ-tui --ui-replay tests/data/basic_session.jsonl --headless`)"
+            );
+        }
+    }
+}
@@ -8,7 +8,10 @@ use crate::app::{
     self, App, CTCSS_TONES, DTCS_CODES, DuplexDir, Focus, InputMode, LogLevel, OffsetEditPhase,
     PowerLevel, ToneEditPhase, ToneType,
 };
+use crate::explorer::{self, RawOutcome, RawResult};
 use crate::message::{GpsPosition, Vfo, VfoState};
+use civ_protocol::command::duplex_sub;
+use civ_protocol::gps::GPS_MESSAGE_LEN;
 
 pub fn draw(frame: &mut Frame, app: &App) {
     let area = frame.area();
@@ -64,25 +67,41 @@ pub fn draw(frame: &mut Frame, app: &App) {
     );
     frame.render_widget(Paragraph::new(vfo_b_line), chunks[2]);
 
-    // GPS row.
-    let gps_line = render_gps_row(&app.radio_state.gps_position);
+    // GPS row (doubles as the GPS message prompt).
+    let gps_line = if app.input_mode == InputMode::Editing(Focus::GpsMessage) {
+        render_gps_message_prompt(app)
+    } else {
+        render_gps_row(
+            &app.radio_state.gps_position,
+            app.input_mode == InputMode::Editing(Focus::Gps),
+        )
+    };
     frame.render_widget(Paragraph::new(gps_line), chunks[3]);
 
-    // Error log.
-    render_error_log(frame, app, chunks[4]);
+    // Error log, or the raw explorer in its place while it is open.
+    if app.input_mode == InputMode::Editing(Focus::Explorer) {
+        render_explorer(frame, app, chunks[4]);
+    } else {
+        render_error_log(frame, app, chunks[4]);
+    }
 
-    // Help bar: left-aligned help text + right-aligned stats.
+    // Help bar: left-aligned help text + right-aligned stats, with a gap
+    // between them and a column of margin at the right edge.
     let help_area = chunks[5];
+    let stats = render_stats(app);
     let help_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Min(0), Constraint::Length(62)])
+        .constraints([
+            Constraint::Min(0),
+            Constraint::Length(2),
+            Constraint::Length(stats.width() as u16),
+            Constraint::Length(1),
+        ])
         .split(help_area);
 
-    let help = render_help(app);
+    let help = render_help(app, help_chunks[0].width as usize);
     frame.render_widget(Paragraph::new(help), help_chunks[0]);
-
-    let stats = render_stats(app);
-    frame.render_widget(Paragraph::new(stats), help_chunks[1]);
+    frame.render_widget(Paragraph::new(stats), help_chunks[2]);
 }
 
 fn render_compact_meters(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
@@ -212,9 +231,17 @@ fn render_compact_meter(
     Line::from(spans)
 }
 
-fn render_gps_row(gps: &Option<GpsPosition>) -> Line<'static> {
+fn render_gps_row(gps: &Option<GpsPosition>, is_selected: bool) -> Line<'static> {
+    let label_style = if is_selected {
+        Style::default().fg(Color::Black).bg(Color::White)
+    } else {
+        Style::default().fg(Color::White)
+    };
     match gps {
-        None => Line::from(Span::styled(" GPS: No Fix", Style::default())),
+        None => Line::from(vec![
+            Span::styled(" GPS:", label_style),
+            Span::styled(" No Fix", Style::default()),
+        ]),
         Some(p) => {
             // Latitude: convert decimal degrees back to dd°mm.mmm'N/S
             let lat_abs = p.latitude.abs();
@@ -239,7 +266,8 @@ fn render_gps_row(gps: &Option<GpsPosition>) -> Line<'static> {
             );
 
             Line::from(vec![
-                Span::styled(" GPS: ", Style::default().fg(Color::White)),
+                Span::styled(" GPS:", label_style),
+                Span::styled(" ", Style::default()),
                 Span::styled(lat_str, Style::default().fg(Color::Green)),
                 Span::styled("  ", Style::default()),
                 Span::styled(lon_str, Style::default().fg(Color::Cyan)),
@@ -256,6 +284,40 @@ fn render_gps_row(gps: &Option<GpsPosition>) -> Line<'static> {
     }
 }
 
+/// Render the GPS message prompt in place of the GPS row.
+fn render_gps_message_prompt(app: &App) -> Line<'static> {
+    let text = &app.gps_message_edit;
+    let remaining = GPS_MESSAGE_LEN.saturating_sub(text.chars().count());
+    // The cursor cell takes one of the remaining slots; hide it once full.
+    let cursor = if remaining > 0 { " " } else { "" };
+    Line::from(vec![
+        Span::styled(
+            " GPS Msg:",
+            Style::default().fg(Color::Black).bg(Color::White),
+        ),
+        Span::styled(" [", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            text.clone(),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(cursor, Style::default().bg(Color::Yellow)),
+        Span::styled(
+            format!("{}]", " ".repeat(remaining.saturating_sub(1))),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            if app.gps_message_ready {
+                format!("  {}/{GPS_MESSAGE_LEN}", text.chars().count())
+            } else {
+                "  reading\u{2026}".to_string()
+            },
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])
+}
+
 fn render_vfo_row(vfo: Vfo, state: &VfoState, is_selected: bool, app: &App) -> Line<'static> {
     let label_style = if is_selected {
         Style::default().fg(Color::Black).bg(Color::White)
@@ -548,9 +610,9 @@ fn tone_edit_display(app: &App) -> String {
 /// Offset format: `+  5 000 000` (10 chars for the number, space-grouped).
 fn duplex_spans(state: &VfoState, base_style: Style) -> Vec<Span<'static>> {
     match state.duplex {
-        Some(0x10) => vec![Span::styled("\u{25C6}    Simplex", base_style)],
-        Some(dir @ (0x11 | 0x12)) => {
-            let (sign, color) = if dir == 0x12 {
+        Some(duplex_sub::SIMPLEX) => vec![Span::styled("\u{25C6}    Simplex", base_style)],
+        Some(dir @ (duplex_sub::MINUS | duplex_sub::PLUS)) => {
+            let (sign, color) = if dir == duplex_sub::PLUS {
                 ("+", Color::Yellow)
             } else {
                 ("-", Color::Cyan)
@@ -642,11 +704,12 @@ fn render_error_log(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let lines: Vec<Line<'static>> = app.error_log[start..]
         .iter()
         .map(|(timestamp, level, msg)| {
-            let elapsed = timestamp.elapsed().as_secs();
+            let elapsed = app.now.saturating_duration_since(*timestamp).as_secs();
             let mins = elapsed / 60;
             let secs = elapsed % 60;
             let color = match level {
                 LogLevel::Error => Color::Red,
+                LogLevel::Warn => Color::Yellow,
                 LogLevel::Info => Color::Blue,
             };
             Line::from(Span::styled(
@@ -659,10 +722,102 @@ fn render_error_log(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     frame.render_widget(Paragraph::new(lines), area);
 }
 
-fn render_help(app: &App) -> Line<'static> {
+/// Render the raw explorer: input line (or write confirmation) above the history.
+fn render_explorer(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let ex = &app.explorer;
+    let block = Block::default()
+        .title(" Raw Explorer (0x16 / 0x1A 05) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    if inner.height == 0 {
+        return;
+    }
+
+    let prompt = match &ex.pending_write {
+        Some((target, value)) => Line::from(vec![
+            Span::styled(
+                " DANGER ",
+                Style::default()
+                    .fg(Color::White)
+                    .bg(Color::Red)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    " write {} to {target}? Unknown values can misconfigure the radio. [y] send, any other key cancels",
+                    explorer::hex(value)
+                ),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        None => Line::from(vec![
+            Span::styled(" > ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                ex.input.clone(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" ", Style::default().bg(Color::Yellow)),
+        ]),
+    };
+
+    let mut lines = vec![prompt];
+    if let Some(status) = &ex.status {
+        lines.push(Line::from(Span::styled(
+            format!("   {status}"),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    // History, newest at the bottom, shifted back by the scroll offset.
+    let visible = (inner.height as usize).saturating_sub(lines.len());
+    let end = ex.history.len().saturating_sub(ex.scroll);
+    let start = end.saturating_sub(visible);
+    lines.extend(ex.history[start..end].iter().map(render_raw_result));
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_raw_result(result: &RawResult) -> Line<'static> {
+    let target = Span::styled(
+        format!("  {:<12}", result.target.to_string()),
+        Style::default().fg(Color::Cyan),
+    );
+    match &result.outcome {
+        RawOutcome::Read(value) => {
+            let mut spans = vec![
+                target,
+                Span::raw(format!("read   {}", explorer::hex(value))),
+            ];
+            if let Some(decoded) = explorer::decode(result.target, value) {
+                spans.push(Span::styled(
+                    format!("  ({decoded})"),
+                    Style::default().fg(Color::Green),
+                ));
+            }
+            Line::from(spans)
+        }
+        RawOutcome::Written(value) => Line::from(vec![
+            target,
+            Span::styled(
+                format!("wrote  {}", explorer::hex(value)),
+                Style::default().fg(Color::Magenta),
+            ),
+        ]),
+        RawOutcome::Failed(reason) => Line::from(vec![
+            target,
+            Span::styled(format!("failed {reason}"), Style::default().fg(Color::Red)),
+        ]),
+    }
+}
+
+/// Help text for the current mode, cut to `width` at a whole item.
+fn render_help(app: &App, width: usize) -> Line<'static> {
     let help_text: String = match app.input_mode {
         InputMode::Normal => {
-            "  [Q]uit  [F]req  [M]ode  [W]idth  [V]FO  [A]F/Vol  [S]ql  [P]wr  [O]ffset  [T]x Tone  [R]x Tone  +/- Vol  [0] Mute".to_string()
+            "  [Q]uit  [F]req  [M]ode  [W]idth  [V]FO  [A]F/Vol  [S]ql  [P]wr  [O]ffset  [T]x Tone  [R]x Tone  [G]PS  +/- Vol  [0] Mute  [F9] Raw".to_string()
         }
         InputMode::Editing(Focus::Frequency) => {
             "  \u{2190}\u{2192} move cursor  \u{2191}\u{2193} change digit  0-9 type digit  Enter confirm  Esc cancel".to_string()
@@ -700,6 +855,17 @@ fn render_help(app: &App) -> Line<'static> {
                 },
             }
         }
+        InputMode::Editing(Focus::Gps) => "  [M]essage  Esc cancel".to_string(),
+        InputMode::Editing(Focus::GpsMessage) => {
+            "  type message (printable ASCII)  Backspace delete  Enter confirm  Esc cancel".to_string()
+        }
+        InputMode::Editing(Focus::Explorer) => {
+            if app.explorer.pending_write.is_some() {
+                "  [y] send write  any other key cancels".to_string()
+            } else {
+                "  \"16 5D\" / \"1A 05 0123\" read  \"... = 01\" write  Enter send  \u{2191}\u{2193} PgUp/PgDn scroll  Esc close".to_string()
+            }
+        }
         InputMode::Editing(Focus::Offset) => {
             match app.offset_edit_phase {
                 OffsetEditPhase::SelectDirection => {
@@ -712,8 +878,15 @@ fn render_help(app: &App) -> Line<'static> {
         }
     };
 
+    // Items are separated by two spaces; drop whole items from the end
+    // rather than let the area edge cut one in half.
+    let mut fitted = help_text.as_str();
+    while Span::raw(fitted).width() > width {
+        fitted = fitted.rfind("  ").map_or("", |i| &fitted[..i]);
+    }
+
     Line::from(Span::styled(
-        help_text.to_string(),
+        fitted.to_string(),
         Style::default().fg(Color::Magenta),
     ))
 }
@@ -723,9 +896,9 @@ fn render_stats(app: &App) -> Line<'static> {
     let tx = app.radio_state.tx_bits_per_sec;
     let rx = app.radio_state.rx_bits_per_sec;
     let total = tx + rx;
-    let total_pct = if baud > 0 { total * 100 / baud } else { 0 };
-    let tx_pct = if baud > 0 { tx * 100 / baud } else { 0 };
-    let rx_pct = if baud > 0 { rx * 100 / baud } else { 0 };
+    let total_pct = (total * 100).checked_div(baud).unwrap_or(0);
+    let tx_pct = (tx * 100).checked_div(baud).unwrap_or(0);
+    let rx_pct = (rx * 100).checked_div(baud).unwrap_or(0);
 
     Line::from(vec![
         Span::raw(format!("Baud {baud} ({total_pct:>3}%)  ")),
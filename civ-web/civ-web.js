@@ -1,5 +1,6 @@
 import init, {
     FrameBuffer,
+    PollPlanner,
     encode_read_frequency,
     encode_read_mode,
     encode_set_frequency,
@@ -7,9 +8,6 @@ import init, {
     encode_select_vfo,
     encode_power_on,
     encode_power_off,
-    encode_read_level,
-    encode_read_s_meter,
-    encode_read_gps,
     encode_set_level,
     encode_read_tone_mode,
     encode_set_tone_mode,
@@ -25,6 +23,10 @@ import init, {
 const LEVEL_AF = 0x01;
 const LEVEL_SQUELCH = 0x03;
 
+// Poll planner settings.
+const POLL_BASE_MS = 500;
+const POLL_MAX_FRAMES_PER_TICK = 4;
+
 // CI-V various sub-command constants.
 const VARIOUS_TONE_SQUELCH_FUNC = 0x5d;
 
@@ -35,7 +37,7 @@ let port = null;
 let reader = null;
 let frameBuffer = null;
 let pollTimer = null;
-let gpsPollTimer = null;
+let pollPlanner = null;
 let currentVfo = "A";
 let radioVfo = "A";
 let disconnecting = false;
@@ -392,8 +394,13 @@ async function startReading() {
 // ── Polling ─────────────────────────────────────────────────────────────────
 
 function startPolling() {
+    stopPolling();
     let pollRunning = false;
-    let gpsPollRunning = false;
+
+    // The planner decides what is due each tick. It skips slots missed while the
+    // tab was throttled and caps frames per tick so a refocus can't flood the adapter.
+    pollPlanner = new PollPlanner(POLL_BASE_MS, POLL_MAX_FRAMES_PER_TICK);
+    pollPlanner.on_visibility_change(document.hidden);
 
     pollTimer = setInterval(async () => {
         if (!port?.writable || disconnecting) return;
@@ -401,26 +408,13 @@ function startPolling() {
         if (pollRunning || cmdQueue.length > 0 || cmdInFlight) return;
         pollRunning = true;
         try {
-            const sResp = await sendCommand(encode_read_s_meter());
-            if (sResp) handleResponse(sResp);
-            const afResp = await sendCommand(encode_read_level(LEVEL_AF));
-            if (afResp) handleResponse(afResp);
-            const sqlResp = await sendCommand(encode_read_level(LEVEL_SQUELCH));
-            if (sqlResp) handleResponse(sqlResp);
+            for (const frame of pollPlanner.tick(performance.now())) {
+                const resp = await sendCommand(frame);
+                if (resp) handleResponse(resp);
+            }
         } catch (_) {}
         pollRunning = false;
-    }, 500);
-
-    gpsPollTimer = setInterval(async () => {
-        if (!port?.writable || disconnecting) return;
-        if (gpsPollRunning || cmdQueue.length > 0 || cmdInFlight) return;
-        gpsPollRunning = true;
-        try {
-            const gpsResp = await sendCommand(encode_read_gps());
-            if (gpsResp) handleResponse(gpsResp);
-        } catch (_) {}
-        gpsPollRunning = false;
-    }, 5000);
+    }, POLL_BASE_MS);
 }
 
 function stopPolling() {
@@ -428,12 +422,17 @@ function stopPolling() {
         clearInterval(pollTimer);
         pollTimer = null;
     }
-    if (gpsPollTimer) {
-        clearInterval(gpsPollTimer);
-        gpsPollTimer = null;
+    if (pollPlanner) {
+        pollPlanner.free();
+        pollPlanner = null;
     }
 }
 
+// Switch the planner to its reduced background profile while the tab is hidden.
+document.addEventListener("visibilitychange", () => {
+    if (pollPlanner) pollPlanner.on_visibility_change(document.hidden);
+});
+
 // ── Response handling ───────────────────────────────────────────────────────
 
 function handleResponse(resp) {
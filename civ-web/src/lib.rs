@@ -1,17 +1,30 @@
 use wasm_bindgen::prelude::*;
 
-use civ_protocol::command::{cmd, meter_sub, tone_sub, various_sub, Command};
+use civ_protocol::bcd;
+use civ_protocol::command::{
+    cmd, duplex_sub, gps_sub, meter_sub, tone_sub, various_sub, Command,
+};
 use civ_protocol::frequency::Frequency;
 use civ_protocol::mode::OperatingMode;
 use civ_protocol::protocol::{Frame, PREAMBLE};
 use civ_protocol::response::{self, Response};
 
+mod poll;
+
+pub use poll::PollPlanner;
+
 /// Accumulates raw bytes from WebSerial and extracts complete CI-V frames.
 #[wasm_bindgen]
 pub struct FrameBuffer {
     buf: Vec<u8>,
 }
 
+impl Default for FrameBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[wasm_bindgen]
 impl FrameBuffer {
     #[wasm_bindgen(constructor)]
@@ -128,9 +141,9 @@ impl FrameBuffer {
             Response::Duplex(dir) => {
                 js_sys::Reflect::set(&obj, &"type".into(), &"duplex".into())?;
                 let label = match dir {
-                    0x10 => "simplex",
-                    0x11 => "dup-",
-                    0x12 => "dup+",
+                    duplex_sub::SIMPLEX => "simplex",
+                    duplex_sub::MINUS => "dup-",
+                    duplex_sub::PLUS => "dup+",
                     _ => "unknown",
                 };
                 js_sys::Reflect::set(&obj, &"direction".into(), &JsValue::from_str(label))?;
@@ -194,6 +207,22 @@ impl FrameBuffer {
                     &JsValue::from_f64(gps.speed_tenths as f64 / 10.0),
                 )?;
             }
+            Response::GpsMessage(text) => {
+                js_sys::Reflect::set(&obj, &"type".into(), &"gps_message".into())?;
+                js_sys::Reflect::set(&obj, &"text".into(), &text.into())?;
+            }
+            Response::VariousRaw(sub, value) => {
+                js_sys::Reflect::set(&obj, &"type".into(), &"various_raw".into())?;
+                js_sys::Reflect::set(&obj, &"sub".into(), &JsValue::from_f64(sub as f64))?;
+                let bytes = js_sys::Uint8Array::from(value.as_slice());
+                js_sys::Reflect::set(&obj, &"value".into(), &bytes.into())?;
+            }
+            Response::Setting(item, value) => {
+                js_sys::Reflect::set(&obj, &"type".into(), &"setting".into())?;
+                js_sys::Reflect::set(&obj, &"item".into(), &JsValue::from_f64(item as f64))?;
+                let bytes = js_sys::Uint8Array::from(value.as_slice());
+                js_sys::Reflect::set(&obj, &"value".into(), &bytes.into())?;
+            }
         }
 
         Ok(obj.into())
@@ -231,7 +260,18 @@ impl FrameBuffer {
                 let sub = frame.sub_command.unwrap_or(0);
                 Command::ReadTone(sub)
             }
-            cmd::READ_GPS => Command::ReadGpsPosition,
+            cmd::READ_GPS => match frame.sub_command {
+                Some(gps_sub::MESSAGE) => Command::ReadGpsMessage,
+                _ => Command::ReadGpsPosition,
+            },
+            // The item number is echoed in front of the value.
+            cmd::SETTING => match frame.data.get(..2) {
+                Some(item) => {
+                    let item = bcd::decode_bcd_be(item).map_err(|e| JsValue::from_str(&e.to_string()))?;
+                    Command::ReadSetting(item as u16)
+                }
+                None => return Err(JsValue::from_str("setting response without item number")),
+            },
             _ => return Err(JsValue::from_str(&format!("unknown command byte: {:#04x}", frame.command))),
         };
 
@@ -364,6 +404,25 @@ pub fn encode_read_gps() -> Result<Vec<u8>, JsValue> {
     Ok(frame.to_bytes())
 }
 
+/// Encode a "read GPS message" command.
+#[wasm_bindgen]
+pub fn encode_read_gps_message() -> Result<Vec<u8>, JsValue> {
+    let frame = Command::ReadGpsMessage
+        .to_frame()
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(frame.to_bytes())
+}
+
+/// Encode a "set GPS message" command. Up to 20 printable ASCII characters;
+/// the error names the first offending character and its position.
+#[wasm_bindgen]
+pub fn encode_set_gps_message(text: &str) -> Result<Vec<u8>, JsValue> {
+    let frame = Command::SetGpsMessage(text.to_string())
+        .to_frame()
+        .map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(frame.to_bytes())
+}
+
 /// Encode a "read tone mode" command (reads the tone/squelch function: off, tone, TSQL, DTCS, etc.).
 #[wasm_bindgen]
 pub fn encode_read_tone_mode() -> Result<Vec<u8>, JsValue> {
@@ -472,6 +531,8 @@ fn parse_command(cmd_name: &str, arg_json: &str) -> Result<Command, JsValue> {
         }
         "read_s_meter" => Ok(Command::ReadMeter(meter_sub::S_METER)),
         "read_gps" => Ok(Command::ReadGpsPosition),
+        "read_gps_message" => Ok(Command::ReadGpsMessage),
+        "set_gps_message" => Ok(Command::SetGpsMessage(arg_json.to_string())),
         _ => Err(JsValue::from_str(&format!("unknown command: {cmd_name}"))),
     }
 }
@@ -0,0 +1,274 @@
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+
+use civ_protocol::command::{Command, level_sub, meter_sub};
+
+/// Interval multiplier applied to every slot while the page is hidden.
+const BACKGROUND_SLOWDOWN: f64 = 10.0;
+
+/// A gap longer than this many base intervals means the browser throttled
+/// our timer (background tab, sleep) and missed slots are skipped.
+const MAX_GAP_INTERVALS: f64 = 2.0;
+
+/// One periodically polled command.
+struct PollSlot {
+    command: Command,
+    /// Period as a multiple of the base interval.
+    every: f64,
+    /// Next due time in ms (same clock as `tick`), `None` until the first tick.
+    next_due_ms: Option<f64>,
+}
+
+/// Decides which read commands to send on each poll tick.
+///
+/// The frontend calls `tick()` from a timer with the current time. Slots keep a
+/// drift-free schedule while ticks arrive on time. When the gap since the last
+/// tick exceeds twice the base interval the missed slots are skipped rather than
+/// replayed, and no tick ever returns more than `max_frames_per_tick` frames —
+/// anything beyond the cap is deferred to the following ticks.
+#[wasm_bindgen]
+pub struct PollPlanner {
+    base_interval_ms: f64,
+    max_frames_per_tick: usize,
+    hidden: bool,
+    last_tick_ms: Option<f64>,
+    slots: Vec<PollSlot>,
+    /// Commands that were due but didn't fit under the per-tick cap.
+    deferred: VecDeque<Command>,
+}
+
+#[wasm_bindgen]
+impl PollPlanner {
+    /// Create a planner polling meters every `base_interval_ms` and GPS every
+    /// ten intervals, returning at most `max_frames_per_tick` frames per tick.
+    #[wasm_bindgen(constructor)]
+    pub fn new(base_interval_ms: f64, max_frames_per_tick: usize) -> Self {
+        let slot = |command, every| PollSlot {
+            command,
+            every,
+            next_due_ms: None,
+        };
+        Self {
+            base_interval_ms,
+            max_frames_per_tick: max_frames_per_tick.max(1),
+            hidden: false,
+            last_tick_ms: None,
+            slots: vec![
+                slot(Command::ReadMeter(meter_sub::S_METER), 1.0),
+                slot(Command::ReadLevel(level_sub::AF_LEVEL), 1.0),
+                slot(Command::ReadLevel(level_sub::SQUELCH), 1.0),
+                slot(Command::ReadGpsPosition, 10.0),
+            ],
+            deferred: VecDeque::new(),
+        }
+    }
+
+    /// Return the encoded frames to send for a tick at `now_ms`
+    /// (e.g. `performance.now()`), as an array of `Uint8Array`.
+    pub fn tick(&mut self, now_ms: f64) -> js_sys::Array {
+        let frames = js_sys::Array::new();
+        for command in self.plan(now_ms) {
+            if let Ok(frame) = command.to_frame() {
+                frames.push(&js_sys::Uint8Array::from(frame.to_bytes().as_slice()));
+            }
+        }
+        frames
+    }
+
+    /// Switch between the normal and the reduced background profile.
+    ///
+    /// Call with `document.hidden` from a `visibilitychange` listener.
+    pub fn on_visibility_change(&mut self, hidden: bool) {
+        if self.hidden == hidden {
+            return;
+        }
+        self.hidden = hidden;
+        // Re-anchor so the new profile takes effect from now rather than from
+        // due times computed under the old one.
+        for slot in &mut self.slots {
+            slot.next_due_ms = None;
+        }
+    }
+
+    /// Change the per-tick frame cap.
+    pub fn set_max_frames_per_tick(&mut self, max: usize) {
+        self.max_frames_per_tick = max.max(1);
+    }
+
+    /// Forget the schedule and any deferred frames (e.g. after a reconnect).
+    pub fn reset(&mut self) {
+        self.last_tick_ms = None;
+        self.deferred.clear();
+        for slot in &mut self.slots {
+            slot.next_due_ms = None;
+        }
+    }
+
+    /// Number of frames waiting for a later tick.
+    pub fn deferred(&self) -> usize {
+        self.deferred.len()
+    }
+}
+
+impl PollPlanner {
+    /// Current base interval, stretched while the page is hidden.
+    fn interval_ms(&self) -> f64 {
+        if self.hidden {
+            self.base_interval_ms * BACKGROUND_SLOWDOWN
+        } else {
+            self.base_interval_ms
+        }
+    }
+
+    /// Decide which commands to send for a tick at `now_ms`.
+    fn plan(&mut self, now_ms: f64) -> Vec<Command> {
+        let interval = self.interval_ms();
+        let throttled = self
+            .last_tick_ms
+            .is_some_and(|last| now_ms - last > interval * MAX_GAP_INTERVALS);
+        self.last_tick_ms = Some(now_ms);
+
+        for slot in &mut self.slots {
+            let period = interval * slot.every;
+            let due = match slot.next_due_ms {
+                Some(due) => due,
+                // First tick (or re-anchor): poll now.
+                None => now_ms,
+            };
+            if now_ms < due {
+                slot.next_due_ms = Some(due);
+                continue;
+            }
+
+            slot.next_due_ms = Some(if throttled || now_ms - due >= period {
+                // Skip the missed slots instead of queueing them.
+                now_ms + period
+            } else {
+                due + period
+            });
+
+            if !self.deferred.contains(&slot.command) {
+                self.deferred.push_back(slot.command.clone());
+            }
+        }
+
+        let n = self.deferred.len().min(self.max_frames_per_tick);
+        self.deferred.drain(..n).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE_MS: f64 = 500.0;
+
+    #[test]
+    fn test_first_tick_polls_every_slot() {
+        let mut planner = PollPlanner::new(BASE_MS, 8);
+        let cmds = planner.plan(0.0);
+        assert_eq!(cmds.len(), 4);
+        assert!(cmds.contains(&Command::ReadGpsPosition));
+    }
+
+    #[test]
+    fn test_regular_ticks_keep_cadence() {
+        let mut planner = PollPlanner::new(BASE_MS, 8);
+        planner.plan(0.0);
+        for i in 1..10 {
+            let cmds = planner.plan(i as f64 * BASE_MS);
+            assert_eq!(cmds.len(), 3, "tick {i}");
+            assert!(!cmds.contains(&Command::ReadGpsPosition));
+        }
+        // GPS comes due again after ten intervals.
+        let cmds = planner.plan(10.0 * BASE_MS);
+        assert_eq!(cmds.len(), 4);
+    }
+
+    #[test]
+    fn test_slightly_late_ticks_do_not_drift() {
+        let mut planner = PollPlanner::new(BASE_MS, 8);
+        planner.plan(0.0);
+        // 40 ms late: still due, schedule stays anchored to multiples of 500.
+        assert_eq!(planner.plan(540.0).len(), 3);
+        assert_eq!(planner.plan(1000.0).len(), 3);
+    }
+
+    #[test]
+    fn test_large_gap_skips_missed_slots() {
+        let mut planner = PollPlanner::new(BASE_MS, 8);
+        planner.plan(0.0);
+        planner.plan(BASE_MS);
+
+        // A minute in a background tab: each slot is polled once, not 120 times.
+        let cmds = planner.plan(60_000.0 + BASE_MS);
+        assert_eq!(cmds.len(), 4);
+
+        // And the schedule resumes from the refocus rather than catching up.
+        assert_eq!(planner.plan(60_000.0 + 2.0 * BASE_MS).len(), 3);
+        assert_eq!(planner.deferred(), 0);
+    }
+
+    #[test]
+    fn test_no_burst_larger_than_cap() {
+        let mut planner = PollPlanner::new(BASE_MS, 2);
+        let mut now = 0.0;
+        for gap in [
+            BASE_MS,
+            30_000.0,
+            BASE_MS,
+            5.0 * BASE_MS,
+            120_000.0,
+            BASE_MS,
+        ] {
+            now += gap;
+            assert!(planner.plan(now).len() <= 2, "burst after {gap} ms gap");
+        }
+    }
+
+    #[test]
+    fn test_cap_defers_remainder_to_next_tick() {
+        let mut planner = PollPlanner::new(BASE_MS, 3);
+        let first = planner.plan(0.0);
+        assert_eq!(first.len(), 3);
+        assert_eq!(planner.deferred(), 1);
+
+        // The deferred frame goes out first and isn't duplicated when its slot
+        // comes due again.
+        let second = planner.plan(BASE_MS);
+        assert_eq!(second.len(), 3);
+        assert_eq!(second[0], Command::ReadGpsPosition);
+        assert_eq!(planner.deferred(), 1);
+    }
+
+    #[test]
+    fn test_background_profile_slows_polling() {
+        let mut planner = PollPlanner::new(BASE_MS, 8);
+        planner.plan(0.0);
+        planner.on_visibility_change(true);
+
+        let mut polled = 0;
+        let mut now = 0.0;
+        for _ in 0..20 {
+            now += BASE_MS;
+            polled += planner.plan(now).len();
+        }
+        // Meters are due once per 5 s while hidden; GPS once per 50 s.
+        assert_eq!(polled, 4 + 3);
+
+        planner.on_visibility_change(false);
+        assert_eq!(planner.plan(now + BASE_MS).len(), 4);
+        assert_eq!(planner.plan(now + 2.0 * BASE_MS).len(), 3);
+    }
+
+    #[test]
+    fn test_reset_clears_deferred() {
+        let mut planner = PollPlanner::new(BASE_MS, 1);
+        planner.plan(0.0);
+        assert_eq!(planner.deferred(), 3);
+        planner.reset();
+        assert_eq!(planner.deferred(), 0);
+        assert_eq!(planner.plan(10_000.0).len(), 1);
+    }
+}